@@ -0,0 +1,210 @@
+use crate::{
+    types::types::{Context, Error},
+    utils::{
+        env,
+        jokes::reload_jokes,
+        log::log,
+        registration::{commands_hash, forget_registration, remember_registration},
+    },
+};
+use poise::serenity_prelude as serenity;
+use std::collections::BTreeMap;
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum Scope {
+    Global,
+    Guild,
+}
+
+/// Owner-only bot administration
+#[poise::command(
+    slash_command,
+    owners_only,
+    default_member_permissions = "ADMINISTRATOR",
+    subcommands("register", "unregister", "diff", "cache", "reload", "restart"),
+    subcommand_required
+)]
+pub async fn admin(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Register the bot's commands globally or in this server
+#[poise::command(slash_command, owners_only)]
+pub async fn register(
+    ctx: Context<'_>,
+    #[description = "Where to register"] scope: Scope,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+    let commands = &ctx.framework().options().commands;
+    let guild_id = match scope {
+        Scope::Global => {
+            poise::builtins::register_globally(ctx.http(), commands).await?;
+            None
+        }
+        Scope::Guild => {
+            let guild_id = ctx
+                .guild_id()
+                .ok_or_else(|| {
+                    Error::Validation("Guild registration must be run in a server".to_string())
+                })?;
+            poise::builtins::register_in_guild(ctx.http(), commands, guild_id).await?;
+            Some(guild_id)
+        }
+    };
+    remember_registration(&mut *ctx.data().db.lock().await, guild_id, &commands_hash(commands));
+    log(format!("{} registered commands ({:?})", ctx.author().name, scope));
+    ctx.say(format!("Registered {} commands ({:?}).", commands.len(), scope))
+        .await?;
+    Ok(())
+}
+
+/// Remove every registered command globally or in this server
+#[poise::command(slash_command, owners_only)]
+pub async fn unregister(
+    ctx: Context<'_>,
+    #[description = "Where to unregister"] scope: Scope,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+    let guild_id = match scope {
+        Scope::Global => {
+            serenity::Command::set_global_commands(ctx.http(), Vec::new()).await?;
+            None
+        }
+        Scope::Guild => {
+            let guild_id = ctx
+                .guild_id()
+                .ok_or_else(|| {
+                    Error::Validation("Guild unregistration must be run in a server".to_string())
+                })?;
+            guild_id.set_commands(ctx.http(), Vec::new()).await?;
+            Some(guild_id)
+        }
+    };
+    // Make sure the next startup registers again
+    forget_registration(&mut *ctx.data().db.lock().await, guild_id);
+    log(format!("{} unregistered commands ({:?})", ctx.author().name, scope));
+    ctx.say(format!("Unregistered all commands ({:?}).", scope))
+        .await?;
+    Ok(())
+}
+
+/// Compare the commands registered with Discord against this build
+#[poise::command(slash_command, owners_only)]
+pub async fn diff(
+    ctx: Context<'_>,
+    #[description = "Which registration to compare"] scope: Scope,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+    let registered = match scope {
+        Scope::Global => serenity::Command::get_global_commands(ctx.http()).await?,
+        Scope::Guild => {
+            let guild_id = ctx
+                .guild_id()
+                .ok_or_else(|| {
+                    Error::Validation("Guild diff must be run in a server".to_string())
+                })?;
+            guild_id.get_commands(ctx.http()).await?
+        }
+    };
+
+    let local: BTreeMap<&str, &str> = ctx
+        .framework()
+        .options()
+        .commands
+        .iter()
+        .filter(|c| c.slash_action.is_some() || !c.subcommands.is_empty())
+        .map(|c| (c.name.as_str(), c.description.as_deref().unwrap_or_default()))
+        .collect();
+    let remote: BTreeMap<&str, &str> = registered
+        .iter()
+        .map(|c| (c.name.as_str(), c.description.as_str()))
+        .collect();
+
+    let mut lines = Vec::new();
+    for (name, description) in &local {
+        match remote.get(name) {
+            None => lines.push(format!("+ /{} (not registered)", name)),
+            Some(d) if d != description => lines.push(format!("~ /{} (description changed)", name)),
+            Some(_) => {}
+        }
+    }
+    for name in remote.keys() {
+        if !local.contains_key(name) {
+            lines.push(format!("- /{} (registered but not in this build)", name));
+        }
+    }
+
+    let response = if lines.is_empty() {
+        format!("Registered commands ({:?}) match this build.", scope)
+    } else {
+        format!("```diff\n{}\n```", lines.join("\n"))
+    };
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Show what the bot currently has cached
+#[poise::command(slash_command, owners_only)]
+pub async fn cache(ctx: Context<'_>) -> Result<(), Error> {
+    let cache = ctx.cache();
+    let response = format!(
+        "**Guilds:** {} ({} unavailable)\n**Channels:** {}\n**Users:** {}\n**Shards:** {}\n**Uptime:** {}s",
+        cache.guild_count(),
+        cache.unavailable_guilds().len(),
+        cache.guild_channel_count(),
+        cache.user_count(),
+        cache.shard_count(),
+        ctx.data().stats.uptime().as_secs()
+    );
+    ctx.send(poise::CreateReply::default().content(response).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Reload configuration from .env and the joke files
+#[poise::command(slash_command, owners_only)]
+pub async fn reload(ctx: Context<'_>) -> Result<(), Error> {
+    let mut lines = Vec::new();
+    match env::reload() {
+        Ok(count) => lines.push(format!(
+            "Reloaded {} variables from .env. {} only change on restart.",
+            count,
+            env::RESTART_ONLY.join(", ")
+        )),
+        Err(e) => lines.push(format!("Could not reload .env: {}", e)),
+    }
+
+    match reload_jokes(&ctx.data().jokes) {
+        Ok((collections, jokes)) => lines.push(format!(
+            "Reloaded {} jokes in {} collections",
+            jokes, collections
+        )),
+        Err(e) => lines.push(format!(
+            "Joke files have errors, keeping the current jokes: {}",
+            e
+        )),
+    }
+
+    log(format!("{} reloaded config", ctx.author().name));
+    ctx.send(
+        poise::CreateReply::default()
+            .content(lines.join("\n"))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Shut the bot down so the container restarts it
+#[poise::command(slash_command, owners_only)]
+pub async fn restart(ctx: Context<'_>) -> Result<(), Error> {
+    log(format!("{} requested a restart", ctx.author().name));
+    ctx.send(
+        poise::CreateReply::default()
+            .content("Restarting...")
+            .ephemeral(true),
+    )
+    .await?;
+    ctx.framework().shard_manager().shutdown_all().await;
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use crate::{
+    types::{
+        schedule::{ScheduledEmbed, ScheduledMessage},
+        types::{Context, Error},
+    },
+    utils::{
+        log::log,
+        relay::{check_relay_target, relay},
+        schedules::{add_schedule, save_schedule_file},
+        time::{guild_timezone, parse_duration, parse_time},
+        webhooks::parse_color,
+    },
+};
+use chrono::Utc;
+use poise::serenity_prelude::{self as serenity, CreateAttachment, CreateMessage};
+
+const MAX_DELAY_DAYS: i64 = 7;
+
+/// Post a message in another channel, now or later
+///
+/// Delayed posts become one-off scheduled messages,
+/// so they show up in /schedule list and survive restarts.
+#[poise::command(slash_command, guild_only)]
+#[allow(clippy::too_many_arguments)]
+pub async fn echo(
+    ctx: Context<'_>,
+    #[description = "Channel to post in"]
+    #[channel_types("Text", "News")]
+    channel: serenity::GuildChannel,
+    #[description = "Message text"]
+    #[max_length = 2000]
+    message: Option<String>,
+    #[description = "File to attach"] attachment: Option<serenity::Attachment>,
+    #[description = "Another file to attach"] attachment_2: Option<serenity::Attachment>,
+    #[description = "Embed title"] embed_title: Option<String>,
+    #[description = "Embed text"] embed_description: Option<String>,
+    #[description = "Embed colour, e.g. #ff8800"] embed_color: Option<String>,
+    #[description = "Wait before posting, e.g. 10m or 1h30m"] delay: Option<String>,
+    #[description = "Post at a time in the server timezone, e.g. 18:30"] at: Option<String>,
+) -> Result<(), Error> {
+    let channel = check_relay_target(
+        ctx.serenity_context(),
+        ctx.guild_id(),
+        ctx.author(),
+        channel.id,
+    )
+    .await?;
+
+    let now = Utc::now();
+    let timezone = guild_timezone(channel.guild_id.get());
+    let post_at = match (&delay, &at) {
+        (Some(_), Some(_)) => {
+            return Err(Error::Validation(
+                "Use either a delay or a time, not both.".to_string(),
+            ))
+        }
+        (Some(delay), None) => Some(
+            now.checked_add_signed(parse_duration(delay)?)
+                .ok_or_else(|| Error::Validation("That's too far ahead.".to_string()))?,
+        ),
+        (None, Some(at)) => Some(parse_time(at, now, timezone)?),
+        (None, None) => None,
+    };
+    if let Some(post_at) = post_at {
+        if post_at <= now {
+            return Err(Error::Validation("That time is in the past.".to_string()));
+        }
+        if post_at - now > chrono::Duration::days(MAX_DELAY_DAYS) {
+            return Err(Error::Validation(format!(
+                "I can only wait up to {} days.",
+                MAX_DELAY_DAYS
+            )));
+        }
+    }
+
+    let embed = match (&embed_title, &embed_description) {
+        (None, None) => None,
+        _ => {
+            let colour = match &embed_color {
+                Some(color) => Some(parse_color(color).ok_or_else(|| {
+                    Error::Validation(format!("`{}` isn't a colour I understand.", color))
+                })?),
+                None => None,
+            };
+            Some(ScheduledEmbed {
+                title: embed_title.clone(),
+                description: embed_description.clone(),
+                colour,
+            })
+        }
+    };
+
+    let attachments: Vec<serenity::Attachment> =
+        attachment.into_iter().chain(attachment_2).collect();
+    if message.is_none() && embed.is_none() && attachments.is_empty() {
+        return Err(Error::Validation(
+            "Give me a message, an embed or a file to post.".to_string(),
+        ));
+    }
+
+    ctx.defer_ephemeral().await?;
+    let summary = describe(&message, &embed_title, &attachments);
+
+    let Some(post_at) = post_at else {
+        // Download now: attachment links from the interaction don't live forever
+        let mut builder = CreateMessage::new();
+        for attachment in &attachments {
+            builder = builder.add_file(CreateAttachment::bytes(
+                attachment.download().await?,
+                attachment.filename.clone(),
+            ));
+        }
+        if let Some(message) = &message {
+            builder = builder.content(message);
+        }
+        if let Some(embed) = &embed {
+            builder = builder.embed(embed.build());
+        }
+        let sent = relay(
+            ctx.serenity_context(),
+            &channel,
+            ctx.author(),
+            builder,
+            &summary,
+        )
+        .await?;
+        ctx.say(format!("Posted: {}", sent.link())).await?;
+        return Ok(());
+    };
+
+    // The scheduler re-checks relay access when it posts, in case it changed in the meantime
+    let mut files = Vec::new();
+    for (index, attachment) in attachments.iter().enumerate() {
+        files.push(save_schedule_file(
+            &format!("{}-{}", ctx.id(), index),
+            &attachment.filename,
+            &attachment.download().await?,
+        )?);
+    }
+    let schedule = add_schedule(ScheduledMessage {
+        id: 0,
+        guild_id: channel.guild_id.get(),
+        channel_id: channel.id.get(),
+        author_id: ctx.author().id.get(),
+        content: message.unwrap_or_default(),
+        next_run: post_at,
+        cron: None,
+        failures: 0,
+        embed,
+        files,
+    })?;
+    log(format!(
+        "[Audit] {} ({}) scheduled message #{} in <#{}>: {}",
+        ctx.author().name,
+        ctx.author().id,
+        schedule.id,
+        schedule.channel_id,
+        summary
+    ));
+    ctx.say(format!(
+        "Will post in <#{}> <t:{}:R> (scheduled message #{}).",
+        channel.id,
+        post_at.timestamp(),
+        schedule.id
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Short text for the audit log
+fn describe(
+    message: &Option<String>,
+    embed_title: &Option<String>,
+    attachments: &[serenity::Attachment],
+) -> String {
+    let mut parts = Vec::new();
+    if let Some(message) = message {
+        parts.push(message.clone());
+    }
+    if let Some(title) = embed_title {
+        parts.push(format!("[embed: {}]", title));
+    }
+    for attachment in attachments {
+        parts.push(format!("[file: {}]", attachment.filename));
+    }
+    parts.join(" ")
+}
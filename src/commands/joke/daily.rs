@@ -0,0 +1,113 @@
+use crate::{
+    commands::joke::autocomplete_collection,
+    types::{
+        guild_settings::DailyJoke,
+        types::{Context, Error},
+    },
+    utils::{
+        guild_settings::update_guild_settings,
+        jokes::resolve_collection,
+        permissions::require_admin,
+        relay::check_post_target,
+        time::{guild_timezone, parse_time_of_day},
+    },
+};
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+
+/// Post a joke of the day in a channel
+#[poise::command(slash_command, guild_only, check = "require_admin")]
+pub async fn daily(
+    ctx: Context<'_>,
+    #[description = "Channel to post in"]
+    #[channel_types("Text", "News")]
+    channel: serenity::GuildChannel,
+    #[description = "Time to post every day in this server's timezone, e.g. 09:00 or 9am"]
+    time: String,
+    #[description = "Include a remark, only picking jokes that have one"] with_remark: Option<bool>,
+    #[description = "Joke collection"]
+    #[autocomplete = "autocomplete_collection"]
+    collection: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let channel =
+        check_post_target(ctx.serenity_context(), guild_id, ctx.author(), channel.id).await?;
+    let time = parse_time_of_day(&time)
+        .ok_or_else(|| Error::Validation(format!("I couldn't understand the time `{}`.", time)))?;
+    let collection = match collection {
+        Some(name) => Some(
+            resolve_collection(
+                &ctx.data().jokes.current(),
+                Some(guild_id.get()),
+                Some(&name),
+            )?
+            .name
+            .clone(),
+        ),
+        None => None,
+    };
+
+    let channels = update_guild_settings(guild_id.get(), |settings| {
+        let daily = settings.daily_joke.get_or_insert(DailyJoke {
+            channels: Vec::new(),
+            time,
+            with_remark: false,
+            collection: None,
+            configured_at: None,
+        });
+        if !daily.channels.contains(&channel.id.get()) {
+            daily.channels.push(channel.id.get());
+        }
+        daily.time = time;
+        daily.with_remark = with_remark.unwrap_or(daily.with_remark);
+        daily.collection = collection.or(daily.collection.take());
+        // Start tomorrow rather than straight away if today's time has already gone
+        daily.configured_at = Some(Utc::now());
+        daily
+            .channels
+            .iter()
+            .map(|id| format!("<#{}>", id))
+            .collect::<Vec<_>>()
+            .join(", ")
+    })?;
+
+    let timezone = guild_timezone(guild_id.get());
+    ctx.say(format!(
+        "Joke of the day will be posted at {} ({}) in {}",
+        time.format("%H:%M"),
+        timezone,
+        channels
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Stop posting the joke of the day in a channel, or everywhere
+#[poise::command(slash_command, guild_only, check = "require_admin")]
+pub async fn daily_stop(
+    ctx: Context<'_>,
+    #[description = "Channel to stop posting in; leave out to stop everywhere"]
+    #[channel_types("Text", "News")]
+    channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    update_guild_settings(guild_id, |settings| {
+        if let (Some(daily), Some(channel)) = (settings.daily_joke.as_mut(), &channel) {
+            daily.channels.retain(|id| *id != channel.id.get());
+        }
+        if channel.is_none()
+            || settings
+                .daily_joke
+                .as_ref()
+                .is_some_and(|d| d.channels.is_empty())
+        {
+            settings.daily_joke = None;
+        }
+    })?;
+    let response = match channel {
+        Some(channel) => format!("No more joke of the day in <#{}>", channel.id),
+        None => "Joke of the day is off".to_string(),
+    };
+    ctx.say(response).await?;
+    Ok(())
+}
@@ -0,0 +1,276 @@
+use crate::{
+    types::{
+        joke::{Joke, JokeCollection},
+        types::{Context, Error},
+    },
+    utils::{
+        joke_messages::{plain_jokes, render_joke},
+        jokes::{enabled_collections, next_joke, resolve_collection, search_jokes},
+        ratings::{joke_key, parse_joke_key},
+    },
+};
+use poise::serenity_prelude::{
+    AutocompleteChoice, CreateActionRow, CreateSelectMenu, CreateSelectMenuKind,
+    CreateSelectMenuOption,
+};
+use std::sync::Arc;
+
+pub mod daily;
+pub mod ratings;
+pub mod settings;
+pub mod submit;
+
+/// Suggest the joke collections enabled here
+pub async fn autocomplete_collection<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let partial = partial.to_lowercase();
+    let names: Vec<String> =
+        enabled_collections(&ctx.data().jokes.current(), ctx.guild_id().map(|g| g.get()))
+            .iter()
+            .map(|collection| collection.name.clone())
+            .collect();
+    names
+        .into_iter()
+        .filter(move |name| name.starts_with(&partial))
+}
+
+/// Suggest every loaded joke collection
+pub async fn autocomplete_any_collection<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let partial = partial.to_lowercase();
+    let names: Vec<String> = ctx
+        .data()
+        .jokes
+        .current()
+        .collections()
+        .map(|collection| collection.name.clone())
+        .collect();
+    names
+        .into_iter()
+        .filter(move |name| name.starts_with(&partial))
+}
+
+/// Suggest jokes whose text or tags match what has been typed so far
+pub async fn autocomplete_joke(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    let with_remark = ctx.command().name == "remark";
+    let collections =
+        enabled_collections(&ctx.data().jokes.current(), ctx.guild_id().map(|g| g.get()));
+    search_jokes(&collections, partial, with_remark)
+        .into_iter()
+        .take(25)
+        .map(|(_, collection, joke)| {
+            AutocompleteChoice::new(
+                choice_label(&collection.name, &joke, 100),
+                joke_key(&collection.key, joke.id),
+            )
+        })
+        .collect()
+}
+
+/// Jokes from Jokes for Minecrafters and friends
+#[poise::command(
+    slash_command,
+    subcommands(
+        "tell",
+        "list",
+        "submit::submit",
+        "ratings::favourites",
+        "ratings::top",
+        "submit::queue",
+        "settings::review_channel",
+        "settings::rotation",
+        "daily::daily",
+        "daily::daily_stop",
+        "settings::style",
+        "settings::enable",
+        "settings::disable",
+        "settings::default"
+    ),
+    subcommand_required
+)]
+pub async fn joke(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Get a joke, at random, by number or by searching
+#[poise::command(slash_command)]
+pub async fn tell(
+    ctx: Context<'_>,
+    #[description = "Joke number"] index: Option<u32>,
+    #[description = "Search the jokes' text and tags"]
+    #[autocomplete = "autocomplete_joke"]
+    query: Option<String>,
+    #[description = "Joke collection"]
+    #[autocomplete = "autocomplete_collection"]
+    collection: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|g| g.get());
+    let (jokes, joke) = match (index, query) {
+        (Some(number), _) => {
+            let jokes =
+                resolve_collection(&ctx.data().jokes.current(), guild_id, collection.as_deref())?;
+            let joke = jokes.get(number).cloned().ok_or_else(|| {
+                Error::NotFound(format!(
+                    "Invalid joke number. Please use a number between 1 and {}",
+                    jokes.len()
+                ))
+            })?;
+            (jokes, joke)
+        }
+        (None, Some(query)) => {
+            match joke_from_query(ctx, collection.as_deref(), &query, false).await? {
+                Some(found) => found,
+                None => return Ok(()),
+            }
+        }
+        (None, None) => {
+            let jokes =
+                resolve_collection(&ctx.data().jokes.current(), guild_id, collection.as_deref())?;
+            let joke = next_joke(
+                &jokes,
+                &format!("channel:{}", ctx.channel_id()),
+                guild_id,
+                false,
+            )?
+            .ok_or_else(|| Error::Internal("No jokes found".to_string()))?;
+            (jokes, joke)
+        }
+    };
+
+    ctx.send(render_joke(&jokes, &joke, None, None, plain_jokes(guild_id)).into_reply())
+        .await?;
+    Ok(())
+}
+
+/// Find the joke a `query` option means: a pick from autocomplete, or text that clearly matches one joke.
+/// When several jokes match equally well, offers them in a menu and returns `None`.
+pub async fn joke_from_query(
+    ctx: Context<'_>,
+    collection: Option<&str>,
+    query: &str,
+    with_remark: bool,
+) -> Result<Option<(Arc<JokeCollection>, Joke)>, Error> {
+    let guild_id = ctx.guild_id().map(|g| g.get());
+    let collections = match collection {
+        Some(name) => vec![resolve_collection(
+            &ctx.data().jokes.current(),
+            guild_id,
+            Some(name),
+        )?],
+        None => enabled_collections(&ctx.data().jokes.current(), guild_id),
+    };
+
+    // Autocomplete picks arrive as `collection:number`
+    if let Some((name, number)) = parse_joke_key(query) {
+        let picked = collections
+            .iter()
+            .find(|c| c.key == name)
+            .and_then(|c| Some((c.clone(), c.get(number)?.clone())))
+            .filter(|(_, joke)| !with_remark || !joke.remarks.is_empty());
+        if picked.is_some() {
+            return Ok(picked);
+        }
+    }
+
+    let mut results = search_jokes(&collections, query, with_remark);
+    match results.as_slice() {
+        [] => Err(Error::NotFound(format!("No jokes match `{}`.", query))),
+        [_] => Ok(results.pop().map(|(_, c, joke)| (c, joke))),
+        [best, second, ..] if best.0 > second.0 => Ok(Some((best.1.clone(), best.2.clone()))),
+        _ => {
+            let options = results
+                .iter()
+                .take(10)
+                .map(|(_, c, joke)| {
+                    CreateSelectMenuOption::new(
+                        choice_label(&c.name, joke, 100),
+                        joke_key(&c.key, joke.id),
+                    )
+                })
+                .collect();
+            let custom_id = if with_remark {
+                "joke:pick:remark"
+            } else {
+                "joke:pick"
+            };
+            let menu = CreateSelectMenu::new(custom_id, CreateSelectMenuKind::String { options })
+                .placeholder("Pick a joke");
+            ctx.send(
+                poise::CreateReply::default()
+                    .content(format!(
+                        "{} jokes match `{}`. Which one did you mean?",
+                        results.len(),
+                        query
+                    ))
+                    .components(vec![CreateActionRow::SelectMenu(menu)])
+                    .ephemeral(true),
+            )
+            .await?;
+            Ok(None)
+        }
+    }
+}
+
+/// `#12 What do you call...` cut to fit a menu entry
+fn choice_label(collection: &str, joke: &Joke, max: usize) -> String {
+    let label = format!(
+        "{} #{} {}",
+        collection,
+        joke.id,
+        joke.text.replace('\n', " ")
+    );
+    if label.chars().count() <= max {
+        return label;
+    }
+    let mut cut: String = label.chars().take(max - 1).collect();
+    cut.push('…');
+    cut
+}
+
+/// Show the joke collections and how many jokes each has
+#[poise::command(slash_command)]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|g| g.get());
+    let library = &ctx.data().jokes.current();
+    let enabled: Vec<String> = enabled_collections(library, guild_id)
+        .iter()
+        .map(|collection| collection.name.clone())
+        .collect();
+    let default = resolve_collection(library, guild_id, None)
+        .ok()
+        .map(|collection| collection.name.clone());
+
+    let lines: Vec<String> = library
+        .collections()
+        .map(|collection| {
+            let mut line = format!(
+                "`{}` {}: {} jokes, {} with remarks",
+                collection.name,
+                collection.title,
+                collection.len(),
+                collection.with_remarks()
+            );
+            if default.as_ref() == Some(&collection.name) {
+                line.push_str(" (default)");
+            } else if !enabled.contains(&collection.name) {
+                line.push_str(" (disabled)");
+            }
+            line
+        })
+        .collect();
+    ctx.say(lines.join("\n")).await?;
+    Ok(())
+}
+
+/// A one line summary of a joke for lists
+pub fn joke_line(collection: &str, number: u32, text: &str, score: Option<i64>) -> String {
+    let preview: String = text.replace('\n', " ").chars().take(80).collect();
+    match score {
+        Some(score) => format!("`{}` #{} ({:+}): {}", collection, number, score, preview),
+        None => format!("`{}` #{}: {}", collection, number, preview),
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    commands::joke::joke_line,
+    types::{
+        rating::LeaderboardScope,
+        types::{Context, Error},
+    },
+    utils::{
+        jokes::{community_guild, find_joke},
+        ratings::{all_ratings, favourites as user_favourites, parse_joke_key},
+    },
+};
+use poise::serenity_prelude::CreateAllowedMentions;
+
+/// Show the jokes you starred
+#[poise::command(slash_command)]
+pub async fn favourites(ctx: Context<'_>) -> Result<(), Error> {
+    let lines: Vec<String> = user_favourites(ctx.author().id.get())
+        .iter()
+        .filter_map(|key| find_joke(&ctx.data().jokes.current(), key))
+        .map(|(collection, joke)| joke_line(&collection.name, joke.id, &joke.text, None))
+        .collect();
+    let response = if lines.is_empty() {
+        "You haven't starred any jokes yet. Press ⭐ under a joke to add it.".to_string()
+    } else {
+        lines.join("\n")
+    };
+    ctx.send(
+        poise::CreateReply::default()
+            .content(response)
+            .allowed_mentions(CreateAllowedMentions::new())
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// The best rated jokes
+#[poise::command(slash_command)]
+pub async fn top(
+    ctx: Context<'_>,
+    #[description = "Count votes from this server or everywhere"] scope: Option<LeaderboardScope>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|g| g.get());
+    let scope = match (scope, guild_id) {
+        (_, None) => LeaderboardScope::Overall,
+        (scope, Some(_)) => scope.unwrap_or(LeaderboardScope::Server),
+    };
+    let vote_guild = match scope {
+        LeaderboardScope::Server => guild_id,
+        LeaderboardScope::Overall => None,
+    };
+
+    let mut scored: Vec<(String, i64)> = all_ratings()
+        .into_iter()
+        // Other servers' community jokes stay private to them
+        .filter(|(key, _)| {
+            parse_joke_key(key).is_some_and(|(name, _)| {
+                community_guild(name).is_none_or(|guild| Some(guild) == guild_id)
+            })
+        })
+        .map(|(key, rating)| {
+            let score = rating.score(vote_guild);
+            (key, score)
+        })
+        .filter(|(_, score)| *score > 0)
+        .collect();
+    scored.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let lines: Vec<String> = scored
+        .iter()
+        .filter_map(|(key, score)| {
+            let (collection, joke) = find_joke(&ctx.data().jokes.current(), key)?;
+            Some(joke_line(
+                &collection.name,
+                joke.id,
+                &joke.text,
+                Some(*score),
+            ))
+        })
+        .take(10)
+        .collect();
+    let response = if lines.is_empty() {
+        "No jokes have been rated yet.".to_string()
+    } else {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(place, line)| format!("{}. {}", place + 1, line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    ctx.send(
+        poise::CreateReply::default()
+            .content(response)
+            .allowed_mentions(CreateAllowedMentions::new()),
+    )
+    .await?;
+    Ok(())
+}
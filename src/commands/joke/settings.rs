@@ -0,0 +1,173 @@
+use crate::{
+    commands::joke::{autocomplete_any_collection, autocomplete_collection},
+    types::types::{Context, Error},
+    utils::{
+        guild_settings::update_guild_settings,
+        jokes::{resolve_collection, COMMUNITY},
+        log::log,
+        permissions::require_admin,
+        relay::check_relay_target,
+    },
+};
+use poise::{serenity_prelude as serenity, ChoiceParameter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum JokeStyle {
+    #[name = "embeds"]
+    Embed,
+    #[name = "plain text"]
+    Plain,
+}
+
+/// Turn a joke collection on in this server
+#[poise::command(slash_command, guild_only, check = "require_admin")]
+pub async fn enable(
+    ctx: Context<'_>,
+    #[description = "Joke collection"]
+    #[autocomplete = "autocomplete_any_collection"]
+    collection: String,
+) -> Result<(), Error> {
+    let name = known_collection(ctx, &collection)?;
+    let guild_id = ctx.guild_id().unwrap().get();
+    update_guild_settings(guild_id, |settings| {
+        settings.disabled_joke_collections.retain(|c| c != &name)
+    })?;
+    ctx.say(format!("Enabled joke collection `{}`", name))
+        .await?;
+    Ok(())
+}
+
+/// Turn a joke collection off in this server
+#[poise::command(slash_command, guild_only, check = "require_admin")]
+pub async fn disable(
+    ctx: Context<'_>,
+    #[description = "Joke collection"]
+    #[autocomplete = "autocomplete_collection"]
+    collection: String,
+) -> Result<(), Error> {
+    let name = known_collection(ctx, &collection)?;
+    let guild_id = ctx.guild_id().unwrap().get();
+    update_guild_settings(guild_id, |settings| {
+        if !settings.disabled_joke_collections.contains(&name) {
+            settings.disabled_joke_collections.push(name.clone());
+        }
+        if settings.default_joke_collection.as_ref() == Some(&name) {
+            settings.default_joke_collection = None;
+        }
+    })?;
+    log(format!(
+        "{} disabled joke collection {} in {}",
+        ctx.author().name,
+        name,
+        guild_id
+    ));
+    ctx.say(format!("Disabled joke collection `{}`", name))
+        .await?;
+    Ok(())
+}
+
+/// Choose the collection used when a joke command doesn't name one
+#[poise::command(slash_command, guild_only, check = "require_admin")]
+pub async fn default(
+    ctx: Context<'_>,
+    #[description = "Joke collection"]
+    #[autocomplete = "autocomplete_collection"]
+    collection: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    let name = resolve_collection(
+        &ctx.data().jokes.current(),
+        Some(guild_id),
+        Some(&collection),
+    )?
+    .name
+    .clone();
+    update_guild_settings(guild_id, |settings| {
+        settings.default_joke_collection = Some(name.clone())
+    })?;
+    ctx.say(format!("Jokes now come from `{}` by default", name))
+        .await?;
+    Ok(())
+}
+
+/// Set the channel new joke submissions are posted to for review
+#[poise::command(slash_command, guild_only, check = "require_admin")]
+pub async fn review_channel(
+    ctx: Context<'_>,
+    #[description = "Channel for submissions; leave out to stop posting them"]
+    #[channel_types("Text")]
+    channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let channel_id = match channel {
+        Some(channel) => Some(
+            check_relay_target(
+                ctx.serenity_context(),
+                Some(guild_id),
+                ctx.author(),
+                channel.id,
+            )
+            .await?
+            .id
+            .get(),
+        ),
+        None => None,
+    };
+    update_guild_settings(guild_id.get(), |settings| {
+        settings.joke_review_channel = channel_id
+    })?;
+    let response = match channel_id {
+        Some(id) => format!("Joke submissions will be posted in <#{}>", id),
+        None => "Joke submissions will only show up in /joke queue".to_string(),
+    };
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Choose how each channel works through the jokes
+#[poise::command(slash_command, guild_only, check = "require_admin")]
+pub async fn rotation(
+    ctx: Context<'_>,
+    #[description = "Let better rated jokes come up earlier in each round"] weighted: bool,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    update_guild_settings(guild_id, |settings| {
+        settings.weight_jokes_by_rating = weighted
+    })?;
+    let response = if weighted {
+        "Better rated jokes will now come up sooner. Every joke is still shown once before any repeats."
+    } else {
+        "Jokes now come up in a plain shuffle. Every joke is shown once before any repeats."
+    };
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Show jokes as embeds or in the plain text format
+#[poise::command(slash_command, guild_only, check = "require_admin")]
+pub async fn style(
+    ctx: Context<'_>,
+    #[description = "How jokes are posted in this server"] style: JokeStyle,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    update_guild_settings(guild_id, |settings| {
+        settings.plain_jokes = style == JokeStyle::Plain
+    })?;
+    ctx.say(format!("Jokes will be posted as {}", style.name()))
+        .await?;
+    Ok(())
+}
+
+/// Normalised name of a loaded collection, enabled or not
+fn known_collection(ctx: Context<'_>, collection: &str) -> Result<String, Error> {
+    let name = collection.trim().to_lowercase();
+    if name == COMMUNITY {
+        return Ok(name);
+    }
+    ctx.data()
+        .jokes
+        .current()
+        .get(&name)
+        .map(|collection| collection.name.clone())
+        .ok_or_else(|| Error::NotFound(format!("There is no joke collection called `{}`.", name)))
+}
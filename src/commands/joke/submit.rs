@@ -0,0 +1,181 @@
+use crate::{
+    types::{
+        submission::{JokeSubmission, SubmissionStatus},
+        types::{AppContext, Context, Error},
+    },
+    utils::{
+        bot::send,
+        guild_settings::load_guild_settings,
+        log::log,
+        permissions::require_admin,
+        submissions::{add_submission, guild_submissions},
+    },
+};
+use chrono::Utc;
+use poise::serenity_prelude::{
+    ButtonStyle, CreateActionRow, CreateAllowedMentions, CreateButton, CreateMessage,
+};
+
+/// Most submissions one user can have waiting in a guild
+const MAX_PENDING: usize = 5;
+
+#[derive(Debug, poise::Modal)]
+#[name = "Submit a joke"]
+struct SubmitModal {
+    #[name = "Joke"]
+    #[paragraph]
+    #[max_length = 1500]
+    text: String,
+    #[name = "Remark (optional)"]
+    #[paragraph]
+    #[max_length = 1000]
+    remark: Option<String>,
+}
+
+/// Send in a joke for this server's community collection
+#[poise::command(slash_command, guild_only)]
+pub async fn submit(
+    ctx: AppContext<'_>,
+    #[description = "The joke; leave out to write a multi-line one"] text: Option<String>,
+    #[description = "A remark about the joke"] remark: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    let author_id = ctx.author().id.get();
+    let pending = guild_submissions(guild_id, SubmissionStatus::Pending)
+        .iter()
+        .filter(|s| s.author_id == author_id)
+        .count();
+    if pending >= MAX_PENDING {
+        return Err(Error::Validation(format!(
+            "You already have {} jokes waiting for review.",
+            MAX_PENDING
+        )));
+    }
+
+    let (text, remark) = match text {
+        Some(text) => (text, remark),
+        None => {
+            let modal = poise::execute_modal(
+                ctx,
+                Some(SubmitModal {
+                    text: String::new(),
+                    remark,
+                }),
+                Some(std::time::Duration::from_secs(600)),
+            )
+            .await?;
+            let Some(modal) = modal else {
+                return Ok(());
+            };
+            (modal.text, modal.remark)
+        }
+    };
+    // Slash command options are one line, so allow `\\n` like the old joke files did
+    let text = text.trim().replace("\\n", "\n");
+    if text.is_empty() {
+        return Err(Error::Validation("The joke can't be empty.".to_string()));
+    }
+    let remark = remark
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+
+    let submission = add_submission(JokeSubmission {
+        id: 0,
+        guild_id,
+        author_id,
+        text,
+        remark,
+        status: SubmissionStatus::Pending,
+        submitted_at: Utc::now(),
+        reviewed_by: None,
+        joke_id: None,
+    })?;
+    log(format!(
+        "{} submitted joke #{} in {}",
+        ctx.author().name,
+        submission.id,
+        guild_id
+    ));
+
+    if let Some(channel_id) = load_guild_settings(guild_id).joke_review_channel {
+        let builder = CreateMessage::new()
+            .content(review_content(&submission))
+            .components(vec![review_row(submission.id)])
+            .allowed_mentions(CreateAllowedMentions::new());
+        if let Err(e) = send(ctx.http(), channel_id, builder).await {
+            log(format!(
+                "Could not post joke submission #{} for review: {}",
+                submission.id, e
+            ));
+        }
+    }
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "Thanks! Your joke is submission #{} and will show up once a moderator approves it.",
+                submission.id
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Review jokes waiting for approval
+#[poise::command(slash_command, guild_only, check = "require_admin")]
+pub async fn queue(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    let pending = guild_submissions(guild_id, SubmissionStatus::Pending);
+    if pending.is_empty() {
+        ctx.send(
+            poise::CreateReply::default()
+                .content("No jokes are waiting for review.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+    // One message per submission so each keeps its own buttons; Discord allows 5 follow-ups comfortably
+    for submission in pending.iter().take(5) {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(review_content(submission))
+                .components(vec![review_row(submission.id)])
+                .allowed_mentions(CreateAllowedMentions::new())
+                .ephemeral(true),
+        )
+        .await?;
+    }
+    if pending.len() > 5 {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(format!("...and {} more", pending.len() - 5))
+                .ephemeral(true),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// How a submission looks to moderators
+pub fn review_content(submission: &JokeSubmission) -> String {
+    let mut content = format!(
+        "-# Joke submission #{} from <@{}>\n{}",
+        submission.id, submission.author_id, submission.text
+    );
+    if let Some(remark) = &submission.remark {
+        content.push_str(&format!("\n```\n{}```", remark));
+    }
+    content
+}
+
+fn review_row(id: u64) -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("joke:approve:{}", id))
+            .label("Approve")
+            .style(ButtonStyle::Success),
+        CreateButton::new(format!("joke:reject:{}", id))
+            .label("Reject")
+            .style(ButtonStyle::Danger),
+    ])
+}
@@ -1,8 +1,13 @@
-pub mod clear;
+pub mod admin;
+pub mod echo;
 pub mod joke;
 pub mod logs;
+pub mod permissions;
 pub mod realtime;
+pub mod relay;
 pub mod remark;
+pub mod remind;
 pub mod say;
+pub mod schedule;
 pub mod translate;
 pub mod translate_list;
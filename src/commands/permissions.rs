@@ -0,0 +1,186 @@
+use crate::{
+    types::{
+        permission::PermissionLevel,
+        types::{Context, Error},
+    },
+    utils::{
+        guild_settings::{load_guild_settings, update_guild_settings},
+        log::log,
+        permissions::required_level,
+    },
+};
+use poise::{serenity_prelude as serenity, ChoiceParameter};
+
+async fn autocomplete_command<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let names: Vec<String> = ctx
+        .framework()
+        .options()
+        .commands
+        .iter()
+        .map(|c| c.name.clone())
+        .collect();
+    names.into_iter().filter(move |n| n.starts_with(partial))
+}
+
+/// Manage who can use the bot's commands in this server
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("show", "set", "reset", "add_admin_role", "remove_admin_role"),
+    subcommand_required
+)]
+pub async fn permissions(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Show admin roles and the level each command requires
+#[poise::command(slash_command, guild_only)]
+pub async fn show(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    let settings = load_guild_settings(guild_id);
+
+    let roles = if settings.admin_roles.is_empty() {
+        "none".to_string()
+    } else {
+        settings
+            .admin_roles
+            .iter()
+            .map(|r| format!("<@&{}>", r))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut lines = vec![format!("**Admin roles:** {}", roles)];
+    for command in &ctx.framework().options().commands {
+        let level = required_level(&command.name, Some(guild_id));
+        let marker = if settings.command_levels.contains_key(&command.name) {
+            ""
+        } else {
+            " (default)"
+        };
+        lines.push(format!("`/{}`: {}{}", command.name, level.name(), marker));
+    }
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(lines.join("\n"))
+            .allowed_mentions(serenity::CreateAllowedMentions::new())
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Set the level a command requires
+#[poise::command(slash_command, guild_only)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "Command name"]
+    #[autocomplete = "autocomplete_command"]
+    command: String,
+    #[description = "Required level"] level: PermissionLevel,
+) -> Result<(), Error> {
+    let command = command.trim_start_matches('/').to_string();
+    if !ctx
+        .framework()
+        .options()
+        .commands
+        .iter()
+        .any(|c| c.name == command)
+    {
+        return Err(Error::NotFound(format!("Unknown command /{}", command)));
+    }
+    if command == "permissions" && level < PermissionLevel::Admin {
+        return Err(Error::Validation("/permissions always requires at least Admin".to_string()));
+    }
+
+    let guild_id = ctx.guild_id().unwrap().get();
+    update_guild_settings(guild_id, |settings| {
+        settings.command_levels.insert(command.clone(), level)
+    })?;
+
+    log(format!(
+        "{} set /{} to {} in guild {}",
+        ctx.author().name,
+        command,
+        level.name(),
+        guild_id
+    ));
+    ctx.say(format!("`/{}` now requires {}", command, level.name()))
+        .await?;
+    Ok(())
+}
+
+/// Go back to the default level for a command
+#[poise::command(slash_command, guild_only)]
+pub async fn reset(
+    ctx: Context<'_>,
+    #[description = "Command name"]
+    #[autocomplete = "autocomplete_command"]
+    command: String,
+) -> Result<(), Error> {
+    let command = command.trim_start_matches('/').to_string();
+    let guild_id = ctx.guild_id().unwrap().get();
+    update_guild_settings(guild_id, |settings| {
+        settings.command_levels.remove(&command)
+    })?;
+
+    ctx.say(format!(
+        "`/{}` is back to its default: {}",
+        command,
+        PermissionLevel::default_for(&command).name()
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Let members of a role use admin commands
+#[poise::command(slash_command, guild_only)]
+pub async fn add_admin_role(
+    ctx: Context<'_>,
+    #[description = "Role to grant admin"] role: serenity::Role,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    update_guild_settings(guild_id, |settings| {
+        if !settings.admin_roles.contains(&role.id.get()) {
+            settings.admin_roles.push(role.id.get());
+        }
+    })?;
+
+    log(format!(
+        "{} added admin role {} in guild {}",
+        ctx.author().name,
+        role.name,
+        guild_id
+    ));
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!("<@&{}> is now an admin role", role.id))
+            .allowed_mentions(serenity::CreateAllowedMentions::new()),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Stop a role from using admin commands
+#[poise::command(slash_command, guild_only)]
+pub async fn remove_admin_role(
+    ctx: Context<'_>,
+    #[description = "Role to revoke admin from"] role: serenity::Role,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    update_guild_settings(guild_id, |settings| {
+        settings.admin_roles.retain(|r| *r != role.id.get())
+    })?;
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!("<@&{}> is no longer an admin role", role.id))
+            .allowed_mentions(serenity::CreateAllowedMentions::new()),
+    )
+    .await?;
+    Ok(())
+}
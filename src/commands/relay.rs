@@ -0,0 +1,27 @@
+use crate::{
+    types::types::{Context, Error},
+    utils::{guild_settings::update_guild_settings, log::log},
+};
+
+/// Allow or block /say and echo in this server
+#[poise::command(slash_command, guild_only)]
+pub async fn relay(
+    ctx: Context<'_>,
+    #[choices("on", "off")] value: &'static str,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    update_guild_settings(guild_id, |settings| {
+        settings.relay_disabled = value == "off"
+    })?;
+
+    let status = if value == "on" { "enabled" } else { "disabled" };
+    log(format!(
+        "{} {} relaying in guild {}",
+        ctx.author().name,
+        status,
+        guild_id
+    ));
+    ctx.say(format!("Making the bot say things is now {}", status))
+        .await?;
+    Ok(())
+}
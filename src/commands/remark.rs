@@ -1,75 +1,61 @@
 use crate::{
-    types::types::{AppContext, Error},
-    utils::env
-};
-use rand::Rng;
-use std::{
-    fs::File,
-    io::{self, BufRead},
-    path::Path
+    commands::joke::{autocomplete_collection, autocomplete_joke, joke_from_query},
+    types::types::{Context, Error},
+    utils::{
+        joke_messages::{plain_jokes, render_joke},
+        jokes::{next_joke, resolve_collection},
+    },
 };
+use rand::seq::IndexedRandom;
 
 /// Get a Joke for Minecrafters along with a snarky redditor remark
 #[poise::command(slash_command)]
 pub async fn remark(
-    ctx: AppContext<'_>,
-    #[description = "Joke number"] index: Option<usize>,
+    ctx: Context<'_>,
+    #[description = "Joke number"] index: Option<u32>,
+    #[description = "Search the jokes' text and tags"]
+    #[autocomplete = "autocomplete_joke"]
+    query: Option<String>,
+    #[description = "Joke collection"]
+    #[autocomplete = "autocomplete_collection"]
+    collection: Option<String>,
 ) -> Result<(), Error> {
-    let joke = get_joke(index)?;
-    ctx.say(joke).await?;
-    Ok(())
-}
-
-fn get_joke(index: Option<usize>) -> Result<String, Error> {
-    let full_path = env::static_path() + "jokes/jokesandremarks.md";
-    let path = Path::new(&full_path);
-    let file = File::open(path).map_err(|e| Error::from(e))?;
-    let reader = io::BufReader::new(file);
-
-    let mut all_lines = Vec::new();
-    for line in reader.lines() {
-        let line = line.map_err(|e| Error::from(e))?;
-        if !line.trim().is_empty() {
-            let processed_line = line.replace("\\n", "\n");
-            all_lines.push(processed_line);
+    let guild_id = ctx.guild_id().map(|g| g.get());
+    let (jokes, joke) = match (index, query) {
+        (Some(number), _) => {
+            let jokes =
+                resolve_collection(&ctx.data().jokes.current(), guild_id, collection.as_deref())?;
+            let joke = jokes
+                .get(number)
+                .filter(|joke| !joke.remarks.is_empty())
+                .cloned()
+                .ok_or_else(|| {
+                    Error::NotFound(format!("Joke #{} doesn't have a remark", number))
+                })?;
+            (jokes, joke)
         }
-    }
-
-    // Group odd-numbered lines with their following lines
-    let mut joke_pairs = Vec::new();
-    for i in (0..all_lines.len()).step_by(2) {
-        if i + 1 < all_lines.len() {
-            joke_pairs.push((all_lines[i].clone(), all_lines[i + 1].clone()));
-        }
-    }
-
-    if joke_pairs.is_empty() {
-        return Err(Error::from("No joke pairs found"));
-    }
-
-    match index {
-        Some(idx) => {
-            if idx == 0 || idx > joke_pairs.len() {
-                return Err(Error::from(format!(
-                    "Invalid joke number. Please use a number between 1 and {}",
-                    joke_pairs.len()
-                )));
+        (None, Some(query)) => {
+            match joke_from_query(ctx, collection.as_deref(), &query, true).await? {
+                Some(found) => found,
+                None => return Ok(()),
             }
-            let pair_index = idx - 1; // Convert to 0-based index
-            let (line1, line2) = &joke_pairs[pair_index];
-            Ok(format!("-# #{}:\n{}\n```\n{}```", idx, line1, line2))
         }
-        None => {
-            // Select a random joke pair
-            let mut rng = rand::rng();
-            let pair_index = rng.random_range(0..joke_pairs.len());
-            let (line1, line2) = &joke_pairs[pair_index];
-            Ok(format!(
-                "-# #{}:\n{}\n```\n{}```",
-                pair_index + 1,
-                line1,
-                line2
-            ))
+        (None, None) => {
+            let jokes =
+                resolve_collection(&ctx.data().jokes.current(), guild_id, collection.as_deref())?;
+            let joke = next_joke(
+                &jokes,
+                &format!("channel:{}", ctx.channel_id()),
+                guild_id,
+                true,
+            )?
+            .ok_or_else(|| Error::NotFound(format!("No jokes in `{}` have remarks", jokes.name)))?;
+            (jokes, joke)
         }
-    }
+    };
+    let remark = joke.remarks.choose(&mut rand::rng()).unwrap();
+
+    ctx.send(render_joke(&jokes, &joke, Some(remark), None, plain_jokes(guild_id)).into_reply())
+        .await?;
+    Ok(())
 }
@@ -0,0 +1,140 @@
+use crate::{
+    types::{
+        reminder::Reminder,
+        types::{Context, Error},
+    },
+    utils::{
+        reminders::{add_reminder, remove_reminder, update_reminder, user_reminders},
+        time::{guild_timezone, parse_time},
+    },
+};
+use chrono::Utc;
+use chrono_tz::Tz;
+
+/// Most pending reminders one user can have
+const MAX_REMINDERS: usize = 25;
+
+/// Remind yourself of something later
+#[poise::command(
+    slash_command,
+    subcommands("set", "list", "cancel"),
+    subcommand_required
+)]
+pub async fn remind(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Set a reminder
+#[poise::command(slash_command)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "When, e.g. in 2h, tomorrow 9am, friday 17:00 or 2025-12-24 09:00"]
+    when: String,
+    #[description = "What to remind you about"]
+    // Leaves room in Discord's 2000 characters for the mention, timestamp and context link
+    #[max_length = 1800]
+    text: String,
+    #[description = "Send the reminder by DM instead of in this channel"] dm: Option<bool>,
+) -> Result<(), Error> {
+    let timezone = ctx
+        .guild_id()
+        .map(|g| guild_timezone(g.get()))
+        .unwrap_or(Tz::UTC);
+    let now = Utc::now();
+    let remind_at = parse_time(&when, now, timezone)?;
+    if remind_at <= now {
+        return Err(Error::Validation("That time is in the past.".to_string()));
+    }
+    let pending = user_reminders(ctx.author().id.get())
+        .iter()
+        .filter(|r| !r.delivered)
+        .count();
+    if pending >= MAX_REMINDERS {
+        return Err(Error::Validation(format!(
+            "You already have {} reminders. Cancel some first.",
+            MAX_REMINDERS
+        )));
+    }
+
+    let dm = dm.unwrap_or(false);
+    let reminder = add_reminder(Reminder {
+        id: 0,
+        user_id: ctx.author().id.get(),
+        guild_id: ctx.guild_id().map(|g| g.get()),
+        channel_id: ctx.channel_id().get(),
+        context_link: None,
+        text,
+        remind_at,
+        dm,
+        created_at: now,
+        delivered: false,
+        failures: 0,
+    })?;
+
+    let handle = ctx
+        .send(
+            poise::CreateReply::default()
+                .content(format!(
+                    "I'll remind you <t:{}:R> {} (reminder #{})",
+                    remind_at.timestamp(),
+                    if dm { "by DM" } else { "here" },
+                    reminder.id
+                ))
+                .ephemeral(dm),
+        )
+        .await?;
+    // Ephemeral replies can't be linked to
+    if !dm {
+        if let Ok(message) = handle.message().await {
+            update_reminder(reminder.id, |r| r.context_link = Some(message.link()))?;
+        }
+    }
+    Ok(())
+}
+
+/// List your pending reminders
+#[poise::command(slash_command)]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let reminders: Vec<Reminder> = user_reminders(ctx.author().id.get())
+        .into_iter()
+        .filter(|r| !r.delivered)
+        .collect();
+    let response = if reminders.is_empty() {
+        "You have no reminders.".to_string()
+    } else {
+        reminders
+            .iter()
+            .map(|r| r.display())
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    ctx.send(
+        poise::CreateReply::default()
+            .content(response)
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Cancel one of your reminders
+#[poise::command(slash_command)]
+pub async fn cancel(
+    ctx: Context<'_>,
+    #[description = "Reminder number"] id: u64,
+) -> Result<(), Error> {
+    if !user_reminders(ctx.author().id.get())
+        .iter()
+        .any(|r| r.id == id)
+    {
+        return Err(Error::NotFound(format!("You have no reminder #{}.", id)));
+    }
+    remove_reminder(id)?;
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!("Cancelled reminder #{}", id))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
@@ -1,5 +1,7 @@
-use crate::types::types::{Context, Error};
-use anyhow::Result;
+use crate::{
+    types::types::{Context, Error},
+    utils::relay::{audit, check_relay_target, sanitised_mentions},
+};
 
 /// Make the bot say a message
 #[poise::command(slash_command, prefix_command)]
@@ -7,6 +9,20 @@ pub async fn say(
     ctx: Context<'_>,
     #[description = "Message to say"] message: String,
 ) -> Result<(), Error> {
-    ctx.say(message).await?;
+    check_relay_target(
+        ctx.serenity_context(),
+        ctx.guild_id(),
+        ctx.author(),
+        ctx.channel_id(),
+    )
+    .await?;
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(&message)
+            .allowed_mentions(sanitised_mentions()),
+    )
+    .await?;
+    audit(ctx.author(), ctx.channel_id(), ctx.guild_id(), &message);
     Ok(())
 }
@@ -0,0 +1,162 @@
+use crate::{
+    types::{
+        schedule::ScheduledMessage,
+        types::{Context, Error},
+    },
+    utils::{
+        guild_settings::update_guild_settings,
+        log::log,
+        relay::check_relay_target,
+        schedules::{add_schedule, guild_schedules, next_cron_run, parse_cron, remove_schedule},
+        time::{guild_timezone, parse_time},
+    },
+};
+use chrono::Utc;
+use chrono_tz::Tz;
+use poise::serenity_prelude as serenity;
+
+/// Post messages at set times, once or on repeat
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("add", "list", "remove", "timezone"),
+    subcommand_required
+)]
+pub async fn schedule(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Schedule a message, either once at a time or repeating on a cron schedule
+#[poise::command(slash_command, guild_only)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "Channel to post in"]
+    #[channel_types("Text", "News")]
+    channel: serenity::GuildChannel,
+    #[description = "Message text"]
+    #[max_length = 2000]
+    message: String,
+    #[description = "When to post once, e.g. 18:30, 2h or 2025-12-24 09:00"] at: Option<String>,
+    #[description = "Repeat on a cron schedule, e.g. 0 9 * * MON-FRI"] cron: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let channel = check_relay_target(
+        ctx.serenity_context(),
+        Some(guild_id),
+        ctx.author(),
+        channel.id,
+    )
+    .await?;
+    let timezone = guild_timezone(guild_id.get());
+    let now = Utc::now();
+
+    let next_run = match (&at, &cron) {
+        (Some(at), None) => parse_time(at, now, timezone)?,
+        (None, Some(cron)) => {
+            parse_cron(cron)?;
+            next_cron_run(cron, timezone, now)
+                .ok_or_else(|| Error::Validation("That cron schedule never fires.".to_string()))?
+        }
+        _ => {
+            return Err(Error::Validation(
+                "Give either a time to post once or a cron schedule to repeat.".to_string(),
+            ))
+        }
+    };
+    if next_run <= now {
+        return Err(Error::Validation("That time is in the past.".to_string()));
+    }
+
+    let schedule = add_schedule(ScheduledMessage {
+        id: 0,
+        guild_id: guild_id.get(),
+        channel_id: channel.id.get(),
+        author_id: ctx.author().id.get(),
+        content: message,
+        next_run,
+        cron,
+        failures: 0,
+        embed: None,
+        files: Vec::new(),
+    })?;
+
+    log(format!(
+        "[Audit] {} ({}) scheduled message #{} in <#{}>: {}",
+        ctx.author().name,
+        ctx.author().id,
+        schedule.id,
+        schedule.channel_id,
+        schedule.content
+    ));
+    ctx.say(format!("Scheduled {}", schedule.display())).await?;
+    Ok(())
+}
+
+/// List scheduled messages in this server
+#[poise::command(slash_command, guild_only)]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    let schedules = guild_schedules(guild_id);
+    let response = if schedules.is_empty() {
+        "Nothing is scheduled.".to_string()
+    } else {
+        let mut lines: Vec<String> = schedules.iter().take(25).map(|s| s.display()).collect();
+        if schedules.len() > 25 {
+            lines.push(format!("...and {} more", schedules.len() - 25));
+        }
+        lines.join("\n")
+    };
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "-# Timezone: {}\n{}",
+                guild_timezone(guild_id),
+                response
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Remove a scheduled message
+#[poise::command(slash_command, guild_only)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Scheduled message number"] id: u64,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap().get();
+    if !guild_schedules(guild_id).iter().any(|s| s.id == id) {
+        return Err(Error::NotFound(format!(
+            "There is no scheduled message #{}.",
+            id
+        )));
+    }
+    remove_schedule(id)?;
+    log(format!(
+        "{} removed scheduled message #{}",
+        ctx.author().name,
+        id
+    ));
+    ctx.say(format!("Removed scheduled message #{}", id))
+        .await?;
+    Ok(())
+}
+
+/// Set the timezone used for times and cron schedules in this server
+#[poise::command(slash_command, guild_only)]
+pub async fn timezone(
+    ctx: Context<'_>,
+    #[description = "IANA timezone, e.g. Europe/London or America/New_York"] timezone: String,
+) -> Result<(), Error> {
+    let tz: Tz = timezone
+        .trim()
+        .parse()
+        .map_err(|_| Error::Validation(format!("`{}` isn't a timezone I know.", timezone)))?;
+    let guild_id = ctx.guild_id().unwrap().get();
+    update_guild_settings(guild_id, |settings| {
+        settings.timezone = Some(tz.name().to_string())
+    })?;
+    ctx.say(format!("Timezone set to {}", tz.name())).await?;
+    Ok(())
+}
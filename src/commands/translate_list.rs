@@ -4,7 +4,6 @@ use crate::{
     },
     utils::translations::load_translations,
 };
-use anyhow::Result;
 
 #[poise::command(slash_command, prefix_command)]
 pub async fn translate_list(ctx: Context<'_>) -> Result<(), Error> {
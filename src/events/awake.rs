@@ -2,7 +2,6 @@ use crate::{
     types::types::{Data, Error},
     utils::env
 };
-use anyhow::Result;
 use poise::serenity_prelude as serenity;
 use serenity::model::id::ChannelId;
 
@@ -15,6 +14,7 @@ pub async fn awake(
     data_about_bot: &serenity::Ready,
 ) -> Result<(), Error> {
     println!("Logged in as {}", data_about_bot.user.tag());
+    _data.stats.set_ready(true);
 
     rustical_message(
         _ctx,
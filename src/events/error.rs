@@ -0,0 +1,133 @@
+use crate::{
+    events::hooks::record_outcome,
+    types::types::{Context, Data, Error},
+    utils::{bot::send_embed, env, log::log},
+};
+use poise::{serenity_prelude as serenity, FrameworkError};
+use rand::Rng;
+
+/// Central handler for everything that goes wrong inside the framework
+pub async fn on_error(error: FrameworkError<'_, Data, Error>) {
+    if let Some(ctx) = error.ctx() {
+        record_outcome(ctx, "error").await;
+    }
+
+    match error {
+        FrameworkError::Command { ctx, error, .. } => {
+            if error.is_user_error() {
+                reply_user_error(ctx, &error.to_string()).await;
+            } else {
+                report_internal_error(ctx, &error.to_string(), &format!("{:?}", error)).await;
+            }
+        }
+        FrameworkError::CommandCheckFailed { ctx, error, .. } => {
+            let message = match &error {
+                Some(e) if e.is_user_error() => e.to_string(),
+                Some(e) => {
+                    report_internal_error(ctx, &e.to_string(), &format!("{:?}", e)).await;
+                    return;
+                }
+                None => "You can't use this command here.".to_string(),
+            };
+            reply_user_error(ctx, &message).await;
+        }
+        FrameworkError::ArgumentParse {
+            ctx, input, error, ..
+        } => {
+            let message = match input {
+                Some(input) => format!("I couldn't understand `{}`: {}", input, error),
+                None => format!("I couldn't understand that: {}", error),
+            };
+            reply_user_error(ctx, &message).await;
+        }
+        FrameworkError::CommandPanic { ctx, payload, .. } => {
+            let payload = payload.unwrap_or_else(|| "unknown panic".to_string());
+            report_internal_error(ctx, "the command panicked", &payload).await;
+        }
+        FrameworkError::EventHandler { error, event, .. } => {
+            let id = error_id();
+            let detail = format!(
+                "[{}] Error handling {} event: {:?}",
+                id,
+                event.snake_case_name(),
+                error
+            );
+            log(detail.clone());
+            report_to_channel(&id, &detail).await;
+        }
+        other => {
+            if let Err(e) = poise::builtins::on_error(other).await {
+                println!("Error while handling error: {}", e);
+            }
+        }
+    }
+}
+
+/// Short random id that ties a reply to its log entry
+fn error_id() -> String {
+    let mut rng = rand::rng();
+    format!("{:06x}", rng.random_range(0..0x1000000))
+}
+
+async fn reply_user_error(ctx: Context<'_>, message: &str) {
+    let id = error_id();
+    log(format!(
+        "[{}] /{} by {}: {}",
+        id,
+        ctx.command().qualified_name,
+        ctx.author().name,
+        message
+    ));
+    reply(ctx, format!("{}\n-# Error ID: `{}`", message, id)).await;
+}
+
+async fn report_internal_error(ctx: Context<'_>, summary: &str, detail: &str) {
+    let id = error_id();
+    let location = match ctx.guild_id() {
+        Some(guild_id) => format!("guild {} channel {}", guild_id, ctx.channel_id()),
+        None => format!("DM channel {}", ctx.channel_id()),
+    };
+    let full = format!(
+        "[{}] Internal error in /{} by {} ({}) in {}: {}",
+        id,
+        ctx.command().qualified_name,
+        ctx.author().name,
+        ctx.author().id,
+        location,
+        detail
+    );
+    log(full.clone());
+    println!("Internal error ({}): {}", summary, detail);
+    reply(
+        ctx,
+        format!(
+            "Something went wrong on my end. Please try again later.\n-# Error ID: `{}`",
+            id
+        ),
+    )
+    .await;
+    report_to_channel(&id, &full).await;
+}
+
+async fn reply(ctx: Context<'_>, content: String) {
+    let reply = poise::CreateReply::default().content(content).ephemeral(true);
+    if let Err(e) = ctx.send(reply).await {
+        println!("Error while handling error: {}", e);
+    }
+}
+
+/// Post internal errors to `ERROR_CHANNEL` if one is configured
+async fn report_to_channel(id: &str, detail: &str) {
+    let Ok(channel_id) = env::error_channel().parse::<u64>() else {
+        return;
+    };
+    let detail: String = detail.replace("```", "'''").chars().take(4000).collect();
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("Internal error {}", id))
+        .description(format!("```\n{}\n```", detail))
+        .colour(0xe74c3c);
+    let http = serenity::Http::new(&env::discord_token());
+    if let Err(e) = send_embed(&http, channel_id, None, Some(embed)).await {
+        println!("Could not report error {}: {}", id, e);
+    }
+}
@@ -1,27 +1,52 @@
 use crate::{
     events::{
         awake::awake, 
+        interaction::interaction,
         message::message
     },
-    types::types::{Data, Error}
+    types::types::{Data, Error},
+    utils::metrics
 };
-use anyhow::Result;
 use poise::serenity_prelude as serenity;
+use std::time::Instant;
 
 pub async fn event_handler(
     ctx: &serenity::Context,
     event: &serenity::FullEvent,
     _framework: poise::FrameworkContext<'_, Data, Error>,
     data: &Data,
+) -> Result<(), Error> {
+    let started = Instant::now();
+    let result = handle_event(ctx, event, _framework, data).await;
+    metrics::EVENT_DURATION
+        .with_label_values(&[event.snake_case_name()])
+        .observe(started.elapsed().as_secs_f64());
+    result
+}
+
+async fn handle_event(
+    ctx: &serenity::Context,
+    event: &serenity::FullEvent,
+    _framework: poise::FrameworkContext<'_, Data, Error>,
+    data: &Data,
 ) -> Result<(), Error> {
     match event {
         serenity::FullEvent::Ready { data_about_bot, .. } => {
+            if data.stats.is_ready() {
+                metrics::GATEWAY_RECONNECTS.inc();
+            }
             awake(ctx, event, _framework, data, data_about_bot).await?;
         }
         // me when the
         serenity::FullEvent::Message { new_message } => {
             message(ctx, event, _framework, data, new_message).await?;
         }
+        serenity::FullEvent::InteractionCreate { interaction: i } => {
+            interaction(ctx, event, _framework, data, i).await?;
+        }
+        serenity::FullEvent::Resume { .. } => {
+            metrics::GATEWAY_RECONNECTS.inc();
+        }
         _ => {}
     };
     Ok(())
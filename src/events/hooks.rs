@@ -0,0 +1,36 @@
+use crate::{
+    types::types::{Context, Error},
+    utils::{cooldowns::claim_cooldown, metrics, permissions::check_permission},
+};
+use std::time::Instant;
+
+pub async fn pre_command(ctx: Context<'_>) {
+    ctx.data()
+        .stats
+        .record_command(&ctx.command().qualified_name);
+    ctx.set_invocation_data(Instant::now()).await;
+}
+
+/// Global poise check run before every command.
+/// It runs once per parent command too, so anything it starts must only count once per invocation.
+pub async fn command_check(ctx: Context<'_>) -> Result<bool, Error> {
+    check_permission(ctx).await?;
+    claim_cooldown(ctx).await?;
+    Ok(true)
+}
+
+pub async fn post_command(ctx: Context<'_>) {
+    record_outcome(ctx, "success").await;
+}
+
+pub async fn record_outcome(ctx: Context<'_>, outcome: &str) {
+    let name = &ctx.command().qualified_name;
+    metrics::COMMANDS
+        .with_label_values(&[name.as_str(), outcome])
+        .inc();
+    if let Some(started) = ctx.invocation_data::<Instant>().await {
+        metrics::COMMAND_DURATION
+            .with_label_values(&[name.as_str()])
+            .observe(started.elapsed().as_secs_f64());
+    }
+}
@@ -0,0 +1,352 @@
+use crate::{
+    commands::joke::submit::review_content,
+    types::{
+        permission::PermissionLevel,
+        submission::SubmissionStatus,
+        types::{Data, Error},
+    },
+    utils::{
+        joke_messages::{joke_buttons, joke_footer, plain_jokes, render_joke},
+        jokes::find_joke,
+        log::log,
+        permissions::member_level,
+        ratings::{get_rating, joke_key, toggle_favourite, vote},
+        reminders::{get_reminder, remove_reminder, update_reminder},
+        submissions::{get_submission, review_submission},
+        time::max_duration,
+    },
+};
+use chrono::{Duration, Utc};
+use poise::serenity_prelude::{
+    self as serenity, ActionRowComponent, ComponentInteraction, CreateAllowedMentions, CreateEmbed,
+    CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use rand::seq::IndexedRandom;
+
+/// Button presses on messages the bot posted, routed by the prefix of their custom id
+pub async fn interaction(
+    ctx: &serenity::Context,
+    _event: &serenity::FullEvent,
+    framework: poise::FrameworkContext<'_, Data, Error>,
+    data: &Data,
+    interaction: &serenity::Interaction,
+) -> Result<(), Error> {
+    let serenity::Interaction::Component(component) = interaction else {
+        return Ok(());
+    };
+    let parts: Vec<&str> = component.data.custom_id.split(':').collect();
+    match parts.as_slice() {
+        ["reminder", action, rest @ ..] => reminder_button(ctx, component, action, rest).await,
+        ["joke", action @ ("approve" | "reject"), id] => {
+            review_button(ctx, framework, component, *action == "approve", id).await
+        }
+        ["joke", "vote", collection, number, direction] => {
+            vote_button(ctx, data, component, collection, number, *direction == "up").await
+        }
+        ["joke", "remark", collection, number] => {
+            remark_button(ctx, data, component, collection, number).await
+        }
+        ["joke", "pick", rest @ ..] => pick_menu(ctx, data, component, rest == ["remark"]).await,
+        ["joke", "favourite", collection, number] => {
+            favourite_button(ctx, component, collection, number).await
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn reminder_button(
+    ctx: &serenity::Context,
+    component: &ComponentInteraction,
+    action: &str,
+    args: &[&str],
+) -> Result<(), Error> {
+    let reminder = args
+        .first()
+        .and_then(|id| id.parse::<u64>().ok())
+        .and_then(get_reminder);
+    let Some(reminder) = reminder else {
+        return respond_ephemeral(ctx, component, "That reminder has expired.").await;
+    };
+    if reminder.user_id != component.user.id.get() {
+        return respond_ephemeral(ctx, component, "That isn't your reminder.").await;
+    }
+
+    let footer = match action {
+        "snooze" => {
+            let minutes = args
+                .get(1)
+                .and_then(|m| m.parse::<i64>().ok())
+                .unwrap_or(10);
+            let remind_at = Duration::try_minutes(minutes)
+                .filter(|d| *d > Duration::zero() && *d <= max_duration())
+                .and_then(|d| Utc::now().checked_add_signed(d));
+            let Some(remind_at) = remind_at else {
+                return respond_ephemeral(ctx, component, "That isn't a snooze I can do.").await;
+            };
+            let snoozed = update_reminder(reminder.id, |r| {
+                r.remind_at = remind_at;
+                r.delivered = false;
+            })?;
+            if !snoozed {
+                return respond_ephemeral(ctx, component, "That reminder has expired.").await;
+            }
+            log(format!(
+                "{} snoozed reminder #{} for {} minutes",
+                component.user.name, reminder.id, minutes
+            ));
+            format!("Snoozed until <t:{}:t>", remind_at.timestamp())
+        }
+        "done" => {
+            remove_reminder(reminder.id)?;
+            "Done".to_string()
+        }
+        _ => return Ok(()),
+    };
+
+    let content = format!("{}\n-# {}", component.message.content, footer);
+    component
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+    Ok(())
+}
+
+/// A joke chosen from the list of search matches, posted for everyone to see
+async fn pick_menu(
+    ctx: &serenity::Context,
+    data: &Data,
+    component: &ComponentInteraction,
+    with_remark: bool,
+) -> Result<(), Error> {
+    let serenity::ComponentInteractionDataKind::StringSelect { values } = &component.data.kind
+    else {
+        return Ok(());
+    };
+    let guild_id = component.guild_id.map(|g| g.get());
+    let Some((collection, joke)) = values
+        .first()
+        .and_then(|key| find_joke(&data.jokes.current(), key))
+    else {
+        return respond_ephemeral(ctx, component, "That joke is gone.").await;
+    };
+    let remark = with_remark
+        .then(|| joke.remarks.choose(&mut rand::rng()))
+        .flatten();
+    let message = render_joke(
+        &collection,
+        &joke,
+        remark.map(String::as_str),
+        None,
+        plain_jokes(guild_id),
+    );
+    component
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(message.into_response()),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Reveal a remark under a joke that was posted without one, by adding it to the joke's message.
+/// The button goes away, so the remark is only revealed once.
+async fn remark_button(
+    ctx: &serenity::Context,
+    data: &Data,
+    component: &ComponentInteraction,
+    collection: &str,
+    number: &str,
+) -> Result<(), Error> {
+    let Ok(number) = number.parse::<u32>() else {
+        return Ok(());
+    };
+    let key = joke_key(collection, number);
+    let remark = find_joke(&data.jokes.current(), &key)
+        .and_then(|(_, joke)| joke.remarks.choose(&mut rand::rng()).cloned());
+    let Some(remark) = remark else {
+        return respond_ephemeral(ctx, component, "That joke has no remarks any more.").await;
+    };
+
+    let mut response = CreateInteractionResponseMessage::new()
+        .components(vec![joke_buttons(
+            collection,
+            number,
+            &get_rating(&key),
+            false,
+        )])
+        .allowed_mentions(CreateAllowedMentions::new());
+    response = match component.message.embeds.first() {
+        Some(embed) => response.embed(CreateEmbed::from(embed.clone()).field(
+            "Remark",
+            format!("||{}||", remark),
+            false,
+        )),
+        None => response.content(format!("{}\n```\n{}```", component.message.content, remark)),
+    };
+    component
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(response),
+        )
+        .await?;
+    Ok(())
+}
+
+/// A vote under a posted joke; the buttons and embed footer show the new counts
+async fn vote_button(
+    ctx: &serenity::Context,
+    data: &Data,
+    component: &ComponentInteraction,
+    collection: &str,
+    number: &str,
+    up: bool,
+) -> Result<(), Error> {
+    let Ok(number) = number.parse::<u32>() else {
+        return Ok(());
+    };
+    let guild_id = component.guild_id.map(|g| g.get());
+    let key = joke_key(collection, number);
+    let rating = vote(
+        &key,
+        component.user.id.get(),
+        guild_id,
+        if up { 1 } else { -1 },
+    )?;
+
+    // Keep the remark button if the message had one
+    let remark_button = component.message.components.iter().any(|row| {
+        row.components.iter().any(|button| {
+            matches!(button, ActionRowComponent::Button(b)
+                if matches!(&b.data, serenity::ButtonKind::NonLink { custom_id, .. } if custom_id.starts_with("joke:remark:")))
+        })
+    });
+    let mut response = CreateInteractionResponseMessage::new().components(vec![joke_buttons(
+        collection,
+        number,
+        &rating,
+        remark_button,
+    )]);
+    if let (Some(embed), Some((joke_collection, _))) = (
+        component.message.embeds.first(),
+        find_joke(&data.jokes.current(), &key),
+    ) {
+        response = response.embed(
+            CreateEmbed::from(embed.clone()).footer(CreateEmbedFooter::new(joke_footer(
+                &joke_collection,
+                &rating,
+            ))),
+        );
+    }
+    component
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(response),
+        )
+        .await?;
+    Ok(())
+}
+
+async fn favourite_button(
+    ctx: &serenity::Context,
+    component: &ComponentInteraction,
+    collection: &str,
+    number: &str,
+) -> Result<(), Error> {
+    let Ok(number) = number.parse::<u32>() else {
+        return Ok(());
+    };
+    let added = toggle_favourite(component.user.id.get(), &joke_key(collection, number))?;
+    let message = if added {
+        format!(
+            "Added #{} to your favourites. See them with /joke favourites.",
+            number
+        )
+    } else {
+        format!("Removed #{} from your favourites.", number)
+    };
+    respond_ephemeral(ctx, component, &message).await
+}
+
+/// A moderator approving or rejecting a joke submission
+async fn review_button(
+    ctx: &serenity::Context,
+    framework: poise::FrameworkContext<'_, Data, Error>,
+    component: &ComponentInteraction,
+    approved: bool,
+    id: &str,
+) -> Result<(), Error> {
+    let level = match (&component.member, component.guild_id) {
+        _ if framework.options().owners.contains(&component.user.id) => PermissionLevel::Owner,
+        (Some(member), Some(guild_id)) => member_level(guild_id.get(), member, member.permissions),
+        _ => PermissionLevel::Everyone,
+    };
+    if level < PermissionLevel::Admin {
+        return respond_ephemeral(ctx, component, "Only admins can review jokes.").await;
+    }
+    let submission = id.parse::<u64>().ok().and_then(get_submission);
+    let Some(submission) =
+        submission.filter(|s| Some(s.guild_id) == component.guild_id.map(|g| g.get()))
+    else {
+        return respond_ephemeral(ctx, component, "That submission no longer exists.").await;
+    };
+    if submission.status != SubmissionStatus::Pending {
+        return respond_ephemeral(ctx, component, "Someone already reviewed that joke.").await;
+    }
+
+    // Another moderator may have got there in the meantime
+    let submission = match review_submission(submission.id, approved, component.user.id.get()) {
+        Ok(submission) => submission,
+        Err(e) if e.is_user_error() => {
+            return respond_ephemeral(ctx, component, &e.to_string()).await
+        }
+        Err(e) => return Err(e),
+    };
+    let footer = match submission.joke_id {
+        Some(number) => format!(
+            "Approved by <@{}> as community joke #{}",
+            component.user.id, number
+        ),
+        None => format!("Rejected by <@{}>", component.user.id),
+    };
+    log(format!(
+        "[Audit] {} {} joke submission #{}",
+        component.user.name,
+        if approved { "approved" } else { "rejected" },
+        submission.id
+    ));
+    component
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(format!("{}\n-# {}", review_content(&submission), footer))
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+    Ok(())
+}
+
+async fn respond_ephemeral(
+    ctx: &serenity::Context,
+    component: &ComponentInteraction,
+    message: &str,
+) -> Result<(), Error> {
+    component
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(message)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+    Ok(())
+}
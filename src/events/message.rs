@@ -1,16 +1,18 @@
 use crate::{
     types::{
+        permission::PermissionLevel,
         translation::Translation,
         types::{Data, Error},
     },
     utils::{
-        bot::send_message_in_channel,
+        cooldowns::claim_message_cooldown,
         log::log,
+        permissions::{message_author_level, required_level},
+        relay::{check_relay_target, relay_message},
         translations::{get_translation, save_translation},
     },
 };
-use anyhow::Result;
-use poise::serenity_prelude as serenity;
+use poise::{serenity_prelude as serenity, ChoiceParameter};
 use regex::Regex;
 
 pub async fn message(
@@ -26,34 +28,33 @@ pub async fn message(
     let channel_id = new_message.channel_id;
     let content = new_message.content.to_lowercase();
 
-    match content.as_str() {
-        "rustical bot" => {
-            let response = "I'm Rusting it";
-            channel_id.say(&_ctx.http, response).await?;
-            return Ok(());
-        }
-        _ => {}
+    if content == "rustical bot" {
+        let response = "I'm Rusting it";
+        channel_id.say(&_ctx.http, response).await?;
+        return Ok(());
     }
 
-    let _ = test_for_translation(&content);
+    let guild_id = new_message.guild_id.map(|g| g.get());
+    let _ = test_for_translation(&content, guild_id);
 
-    handle_echo(&content);
+    handle_echo(_ctx, _framework, new_message).await;
 
     Ok(())
 }
 
-fn test_for_translation(input: &str) -> Option<String> {
+fn test_for_translation(input: &str, guild_id: Option<u64>) -> Option<String> {
     // Use regex to check if the message has text, then more text in parentheses
     let regex = Regex::new(r"^([^\(]+)\s*\((.+)\)").unwrap();
 
-    if let Some(captures) = regex.captures(&input) {
+    if let Some(captures) = regex.captures(input) {
         // Create a new Translation struct where the first text is the abbreviation and the second text is the definition
         let mut translation = Translation {
             abbreviation: captures[1].trim().to_string(),
             definition: captures[2].trim().to_string(),
+            guild_id,
         };
 
-        if let Some(t) = test_for_translation(&translation.definition) {
+        if let Some(t) = test_for_translation(&translation.definition, guild_id) {
             translation.definition = t;
         }
 
@@ -83,15 +84,68 @@ fn test_for_translation(input: &str) -> Option<String> {
     None
 }
 
-fn handle_echo(input: &str) {
-    let echo_regex = Regex::new(r#"^echo ?['"](.+)['"] ?>> ?['"]?<\#(\d+)>['"]?$"#).unwrap();
-    if let Some(captures) = echo_regex.captures(input) {
+async fn handle_echo(
+    ctx: &serenity::Context,
+    framework: poise::FrameworkContext<'_, Data, Error>,
+    new_message: &serenity::Message,
+) {
+    // Match on the original text so the echoed message keeps its casing
+    let echo_regex = Regex::new(r#"(?is)^echo ?['"](.+)['"] ?>> ?['"]?<\#(\d+)>['"]?$"#).unwrap();
+    if let Some(captures) = echo_regex.captures(new_message.content.trim()) {
         let message = captures.get(1).map_or("", |m| m.as_str());
-        let channel_id = captures
+        let Some(channel_id) = captures
             .get(2)
-            .map_or("", |m| m.as_str())
-            .parse::<u64>()
-            .unwrap_or(0);
-        send_message_in_channel(message, channel_id);
+            .and_then(|m| m.as_str().parse::<u64>().ok())
+            .filter(|id| *id != 0)
+        else {
+            return;
+        };
+
+        let author = &new_message.author;
+        let target = serenity::ChannelId::new(channel_id);
+        let result = match check_echo(ctx, framework, new_message, target).await {
+            Ok(channel) => relay_message(ctx, &channel, author, message)
+                .await
+                .map(|_| ()),
+            Err(e) => {
+                log(format!(
+                    "[Audit] Refused echo from {} ({}) to <#{}>: {}",
+                    author.name, author.id, channel_id, e
+                ));
+                Err(e)
+            }
+        };
+        if let Err(e) = result {
+            let reason = if e.is_user_error() {
+                e.to_string()
+            } else {
+                log(format!("Echo to <#{}> failed: {}", channel_id, e));
+                "Something went wrong posting that message.".to_string()
+            };
+            let _ = new_message
+                .reply(&ctx.http, format!("Couldn't echo: {}", reason))
+                .await;
+        }
+    }
+}
+
+/// The checks /echo gets: its permission level and cooldown, then the relay checks
+async fn check_echo(
+    ctx: &serenity::Context,
+    framework: poise::FrameworkContext<'_, Data, Error>,
+    message: &serenity::Message,
+    target: serenity::ChannelId,
+) -> Result<serenity::GuildChannel, Error> {
+    let required = required_level("echo", message.guild_id.map(|g| g.get()));
+    let level = message_author_level(ctx, &framework.options().owners, message).await;
+    if level < required {
+        return Err(Error::Permission(format!(
+            "You need the {} permission level to use echo",
+            required.name()
+        )));
+    }
+    if level < PermissionLevel::Admin {
+        claim_message_cooldown("echo", message)?;
     }
+    check_relay_target(ctx, message.guild_id, &message.author, target).await
 }
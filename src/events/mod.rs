@@ -1,3 +1,6 @@
 pub mod awake;
+pub mod error;
 pub mod handler;
+pub mod hooks;
+pub mod interaction;
 pub mod message;
@@ -1,25 +1,50 @@
 use crate::events::handler::event_handler;
-use crate::types::types::Data;
+use crate::events::{error, hooks};
+use crate::types::joke::JokeStore;
+use crate::types::stats::Stats;
+use crate::types::types::{Data, Error};
 use crate::utils::db::create_or_open_db;
 use crate::utils::env;
-use anyhow::Result;
+use crate::utils::jokes::{convert_markdown, load_jokes};
+use crate::utils::permissions;
+use crate::utils::ratings::migrate_community_keys;
+use crate::utils::registration::register_on_startup;
 use poise::serenity_prelude as serenity;
 use serenity::prelude::*;
-use serenity::Client;
+use serenity::{Client, CreateAllowedMentions};
+use std::sync::Arc;
 
 mod commands;
 mod events;
+mod server;
+mod tasks;
 mod types;
 mod utils;
 
 #[tokio::main]
-async fn main() -> Result<(), anyhow::Error> {
-    dotenv::dotenv().ok();
+async fn main() -> Result<(), Error> {
+    env::load();
+
+    // One-shot migration of the old markdown joke files
+    let args: Vec<String> = std::env::args().collect();
+    if let [_, command, jokes, remarks, output] = args.as_slice() {
+        if command == "convert-jokes" {
+            let count = convert_markdown(jokes, remarks, output)?;
+            println!("Wrote {} jokes to {}", count, output);
+            return Ok(());
+        }
+    }
+
     let token = env::discord_token();
 
-    let db = create_or_open_db(env::data_path() + "real.db");
+    let mut db = create_or_open_db(env::data_path() + "real.db");
+    let stats = Arc::new(Stats::new());
+    let framework_stats = stats.clone();
+    let jokes = Arc::new(JokeStore::new(load_jokes()?));
+    migrate_community_keys(&jokes.current())?;
 
-    let intents = GatewayIntents::GUILD_MESSAGES
+    let intents = GatewayIntents::GUILDS
+        | GatewayIntents::GUILD_MESSAGES
         | GatewayIntents::DIRECT_MESSAGES
         | GatewayIntents::MESSAGE_CONTENT;
 
@@ -27,13 +52,26 @@ async fn main() -> Result<(), anyhow::Error> {
         .options(poise::FrameworkOptions {
             commands: vec![
                 commands::say::say(),
+                commands::echo::echo(),
                 commands::joke::joke(),
                 commands::remark::remark(),
+                commands::remind::remind(),
                 commands::translate::translate(),
                 commands::logs::logs(),
                 commands::realtime::realtime(),
-                commands::clear::clear(),
+                commands::relay::relay(),
+                commands::admin::admin(),
+                commands::permissions::permissions(),
+                commands::schedule::schedule(),
             ],
+            pre_command: |ctx| Box::pin(hooks::pre_command(ctx)),
+            post_command: |ctx| Box::pin(hooks::post_command(ctx)),
+            on_error: |error| Box::pin(error::on_error(error)),
+            command_check: Some(|ctx| Box::pin(hooks::command_check(ctx))),
+            skip_checks_for_owners: true,
+            // Nothing the bot replies with should ping @everyone or roles
+            allowed_mentions: Some(CreateAllowedMentions::new().all_users(true)),
+            owners: permissions::owners(),
             event_handler: |ctx, event, framework, data| {
                 Box::pin(event_handler(ctx, event, framework, data))
             },
@@ -41,9 +79,12 @@ async fn main() -> Result<(), anyhow::Error> {
         })
         .setup(|ctx, _ready, framework| {
             Box::pin(async move {
-                poise::builtins::register_globally(ctx, &framework.options().commands).await?;
+                register_on_startup(&ctx.http, &framework.options().commands, &mut db).await?;
+                tasks::start(ctx, jokes.clone());
                 Ok(Data {
-                    _db: Mutex::new(db),
+                    db: Mutex::new(db),
+                    stats: framework_stats,
+                    jokes,
                 })
             })
         })
@@ -54,8 +95,16 @@ async fn main() -> Result<(), anyhow::Error> {
         .await
         .expect("Could not create client");
 
-    if let Err(e) = client.start().await.map_err(anyhow::Error::from) {
-        println!("Client error: {}", e.to_string());
+    server::start(server::State {
+        stats,
+        http: client.http.clone(),
+        cache: client.cache.clone(),
+        shard_manager: client.shard_manager.clone(),
+        runtime: tokio::runtime::Handle::current(),
+    });
+
+    if let Err(e) = client.start().await.map_err(Error::from) {
+        println!("Client error: {}", e);
         return Err(e);
     }
     Ok(())
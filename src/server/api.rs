@@ -0,0 +1,204 @@
+use crate::{
+    server::{auth, State},
+    types::translation::Translation,
+    utils::{
+        bot::send_message,
+        env,
+        log::{load_all_logs, log, realtime_enabled, set_realtime},
+        translations::{get_translation, load_translations, remove_translation, save_translation},
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{future::Future, pin::Pin};
+use tide::{Body, Next, Request, Response, StatusCode};
+
+#[derive(Deserialize)]
+struct SendMessage {
+    channel_id: u64,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct NewTranslation {
+    abbreviation: String,
+    definition: String,
+    guild_id: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Realtime {
+    enabled: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct LogQuery {
+    count: Option<usize>,
+    q: Option<String>,
+}
+
+#[derive(Serialize)]
+struct LogEntry {
+    timestamp: String,
+    message: String,
+}
+
+/// Reject api requests that don't carry `API_TOKEN`
+pub fn require_token(
+    req: Request<State>,
+    next: Next<'_, State>,
+) -> Pin<Box<dyn Future<Output = tide::Result> + Send + '_>> {
+    Box::pin(async move {
+        if !auth::authorized(&req, &env::api_token()) {
+            return Ok(json_response(
+                StatusCode::Unauthorized,
+                json!({ "error": "missing or invalid api token" }),
+            ));
+        }
+        Ok(next.run(req).await)
+    })
+}
+
+/// POST /api/messages
+pub async fn post_message(mut req: Request<State>) -> tide::Result {
+    let body: SendMessage = req.body_json().await?;
+    if body.content.trim().is_empty() {
+        return Ok(json_response(
+            StatusCode::BadRequest,
+            json!({ "error": "content must not be empty" }),
+        ));
+    }
+
+    let http = req.state().http.clone();
+    let result = req
+        .state()
+        .on_runtime(async move { send_message(&http, body.channel_id, &body.content).await })
+        .await?;
+
+    match result {
+        Ok(message) => {
+            log(format!("API sent a message to channel {}", body.channel_id));
+            Ok(json_response(
+                StatusCode::Created,
+                json!({
+                    "id": message.id.get().to_string(),
+                    "channel_id": message.channel_id.get().to_string(),
+                    "url": message.link(),
+                }),
+            ))
+        }
+        Err(e) => Ok(json_response(
+            StatusCode::BadGateway,
+            json!({ "error": format!("failed to send message: {}", e) }),
+        )),
+    }
+}
+
+/// GET /api/translations
+pub async fn list_translations(_req: Request<State>) -> tide::Result {
+    let all = load_translations().map_err(internal)?;
+    Ok(json_response(StatusCode::Ok, json!(all)))
+}
+
+/// POST /api/translations
+pub async fn add_translation(mut req: Request<State>) -> tide::Result {
+    let body: NewTranslation = req.body_json().await?;
+    let abbreviation = body.abbreviation.trim().to_lowercase();
+    let definition = body.definition.trim().to_lowercase();
+    if abbreviation.is_empty() || definition.is_empty() {
+        return Ok(json_response(
+            StatusCode::BadRequest,
+            json!({ "error": "abbreviation and definition must not be empty" }),
+        ));
+    }
+
+    if get_translation(&abbreviation).map_err(internal)?.is_some() {
+        return Ok(json_response(
+            StatusCode::Conflict,
+            json!({ "error": "translation already exists" }),
+        ));
+    }
+
+    let translation = Translation {
+        abbreviation,
+        definition,
+        guild_id: body.guild_id,
+    };
+    save_translation(&translation).map_err(internal)?;
+    log(format!(
+        "API saved translation: {} -> {}",
+        translation.abbreviation, translation.definition
+    ));
+    Ok(json_response(StatusCode::Created, json!(translation)))
+}
+
+/// DELETE /api/translations/:abbreviation
+pub async fn delete_translation(req: Request<State>) -> tide::Result {
+    let abbreviation = req.param("abbreviation")?.to_lowercase();
+    if remove_translation(&abbreviation).map_err(internal)? {
+        log(format!("API removed translation: {}", abbreviation));
+        Ok(Response::new(StatusCode::NoContent))
+    } else {
+        Ok(json_response(
+            StatusCode::NotFound,
+            json!({ "error": "translation not found" }),
+        ))
+    }
+}
+
+/// GET /api/realtime
+pub async fn get_realtime(_req: Request<State>) -> tide::Result {
+    Ok(json_response(
+        StatusCode::Ok,
+        json!({ "enabled": realtime_enabled() }),
+    ))
+}
+
+/// PUT /api/realtime
+pub async fn put_realtime(mut req: Request<State>) -> tide::Result {
+    let body: Realtime = req.body_json().await?;
+    set_realtime(body.enabled).map_err(internal)?;
+    Ok(json_response(
+        StatusCode::Ok,
+        json!({ "enabled": body.enabled }),
+    ))
+}
+
+/// GET /api/logs
+pub async fn get_logs(req: Request<State>) -> tide::Result {
+    let query: LogQuery = req.query().unwrap_or_default();
+    let search = query
+        .q
+        .map(|q| q.trim().to_lowercase())
+        .filter(|q| !q.is_empty());
+    let count = query.count.unwrap_or(50).clamp(1, 1000);
+
+    let mut logs: Vec<LogEntry> = load_all_logs()
+        .map_err(internal)?
+        .into_iter()
+        .rev()
+        .filter(|log| match &search {
+            None => true,
+            Some(q) => log.message.to_lowercase().contains(q),
+        })
+        .take(count)
+        .map(|log| LogEntry {
+            timestamp: log.timestamp.to_rfc3339(),
+            message: log.message,
+        })
+        .collect();
+    logs.reverse();
+
+    Ok(json_response(StatusCode::Ok, json!(logs)))
+}
+
+fn json_response(status: StatusCode, value: serde_json::Value) -> Response {
+    Response::builder(status)
+        .body(Body::from_json(&value).unwrap_or_else(|_| Body::empty()))
+        .build()
+}
+
+fn internal(e: crate::types::types::Error) -> tide::Error {
+    tide::Error::from_str(StatusCode::InternalServerError, e.to_string())
+}
@@ -0,0 +1,48 @@
+use tide::{http::Cookie, Request};
+
+pub const TOKEN_COOKIE: &str = "rustical_token";
+
+/// Pull a token from the Authorization header, the `token` query parameter or the session cookie
+pub fn request_token<S>(req: &Request<S>) -> Option<String> {
+    if let Some(header) = req.header("Authorization") {
+        if let Some(token) = header.as_str().strip_prefix("Bearer ") {
+            return Some(token.trim().to_string());
+        }
+    }
+    if let Some(token) = query_token(req) {
+        return Some(token);
+    }
+    req.cookie(TOKEN_COOKIE).map(|c| c.value().to_string())
+}
+
+pub fn query_token<S>(req: &Request<S>) -> Option<String> {
+    req.url()
+        .query_pairs()
+        .find(|(k, _)| k == "token")
+        .map(|(_, v)| v.to_string())
+}
+
+/// Whether the request carries `expected`. An empty `expected` means the feature is disabled.
+pub fn authorized<S>(req: &Request<S>, expected: &str) -> bool {
+    if expected.is_empty() {
+        return false;
+    }
+    match request_token(req) {
+        Some(token) => constant_time_eq(token.as_bytes(), expected.as_bytes()),
+        None => false,
+    }
+}
+
+pub fn token_cookie(token: &str) -> Cookie<'static> {
+    let mut cookie = Cookie::new(TOKEN_COOKIE, token.to_string());
+    cookie.set_path("/");
+    cookie.set_http_only(true);
+    cookie
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
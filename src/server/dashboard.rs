@@ -0,0 +1,250 @@
+use crate::{
+    server::{auth, State},
+    types::{dblog::DBLog, translation::Translation},
+    utils::{env, log::load_all_logs, translations::load_translations},
+};
+use chrono::{NaiveDate, TimeZone, Utc};
+use poise::serenity_prelude::GuildId;
+use serde::Deserialize;
+use tide::{http::mime, Request, Response, StatusCode};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 200;
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct TranslationQuery {
+    guild: Option<String>,
+    q: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct LogQuery {
+    q: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    page: Option<usize>,
+    per_page: Option<usize>,
+}
+
+/// Landing page linking to the translation and log views
+pub async fn index(req: Request<State>) -> tide::Result {
+    if !auth::authorized(&req, &env::dashboard_token()) {
+        return Ok(unauthorized());
+    }
+    let body = "<ul>\
+        <li><a href=\"/dashboard/translations\">Translations</a></li>\
+        <li><a href=\"/dashboard/logs\">Logs</a></li>\
+        </ul>";
+    Ok(page(&req, "Rustical", body))
+}
+
+/// Translation dictionary, filterable by guild and searchable by text
+pub async fn translations(req: Request<State>) -> tide::Result {
+    if !auth::authorized(&req, &env::dashboard_token()) {
+        return Ok(unauthorized());
+    }
+    let query: TranslationQuery = req.query().unwrap_or_default();
+    let search = non_empty(&query.q).map(|q| q.to_lowercase());
+    let guild = non_empty(&query.guild);
+
+    let all = load_translations().unwrap_or_default();
+
+    let mut guilds: Vec<u64> = all.iter().filter_map(|t| t.guild_id).collect();
+    guilds.sort_unstable();
+    guilds.dedup();
+
+    let matches: Vec<&Translation> = all
+        .iter()
+        .filter(|t| match guild {
+            None => true,
+            Some("none") => t.guild_id.is_none(),
+            Some(g) => t.guild_id.map(|id| id.to_string()).as_deref() == Some(g),
+        })
+        .filter(|t| match &search {
+            None => true,
+            Some(q) => {
+                t.abbreviation.to_lowercase().contains(q) || t.definition.to_lowercase().contains(q)
+            }
+        })
+        .collect();
+
+    let mut body = String::from("<form method=\"get\"><select name=\"guild\">");
+    body.push_str(&option("", "All guilds", guild.is_none()));
+    body.push_str(&option("none", "No guild", guild == Some("none")));
+    for id in &guilds {
+        let value = id.to_string();
+        body.push_str(&option(
+            &value,
+            &guild_name(req.state(), *id),
+            guild == Some(value.as_str()),
+        ));
+    }
+    body.push_str(&format!(
+        "</select> <input name=\"q\" placeholder=\"Search\" value=\"{}\"> <button>Filter</button></form>",
+        escape(search.as_deref().unwrap_or(""))
+    ));
+    body.push_str(&format!("<p>{} of {} translations</p>", matches.len(), all.len()));
+    body.push_str("<table><tr><th>Abbreviation</th><th>Definition</th><th>Guild</th></tr>");
+    for t in matches {
+        body.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&t.abbreviation),
+            escape(&t.definition),
+            t.guild_id
+                .map(|id| escape(&guild_name(req.state(), id)))
+                .unwrap_or_default()
+        ));
+    }
+    body.push_str("</table>");
+
+    Ok(page(&req, "Translations", &body))
+}
+
+/// Bot logs, newest first, paged and filterable by text and date range
+pub async fn logs(req: Request<State>) -> tide::Result {
+    if !auth::authorized(&req, &env::dashboard_token()) {
+        return Ok(unauthorized());
+    }
+    let query: LogQuery = req.query().unwrap_or_default();
+    let search = non_empty(&query.q).map(|q| q.to_lowercase());
+    let from = non_empty(&query.from).and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+    let to = non_empty(&query.to).and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+    let per_page = query
+        .per_page
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let matches: Vec<DBLog> = load_all_logs()
+        .unwrap_or_default()
+        .into_iter()
+        .rev()
+        .filter(|log| match from {
+            None => true,
+            Some(d) => log.timestamp >= Utc.from_utc_datetime(&d.and_hms_opt(0, 0, 0).unwrap()),
+        })
+        .filter(|log| match to {
+            None => true,
+            Some(d) => log.timestamp.date_naive() <= d,
+        })
+        .filter(|log| match &search {
+            None => true,
+            Some(q) => log.message.to_lowercase().contains(q),
+        })
+        .collect();
+
+    let pages = matches.len().div_ceil(per_page).max(1);
+    let current = query.page.unwrap_or(1).clamp(1, pages);
+
+    let mut body = format!(
+        "<form method=\"get\">\
+        <input name=\"q\" placeholder=\"Search\" value=\"{}\"> \
+        From <input type=\"date\" name=\"from\" value=\"{}\"> \
+        To <input type=\"date\" name=\"to\" value=\"{}\"> \
+        <input type=\"hidden\" name=\"per_page\" value=\"{}\"> \
+        <button>Filter</button></form>",
+        escape(search.as_deref().unwrap_or("")),
+        from.map(|d| d.to_string()).unwrap_or_default(),
+        to.map(|d| d.to_string()).unwrap_or_default(),
+        per_page
+    );
+    body.push_str(&format!(
+        "<p>{} logs, page {} of {}</p>",
+        matches.len(),
+        current,
+        pages
+    ));
+    body.push_str("<table><tr><th>Time (UTC)</th><th>Message</th></tr>");
+    for log in matches.iter().skip((current - 1) * per_page).take(per_page) {
+        body.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td></tr>",
+            log.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            escape(&log.message)
+        ));
+    }
+    body.push_str("</table><p>");
+
+    let link = |target: usize| {
+        let mut url = req.url().clone();
+        let mut params = url.query_pairs_mut();
+        params.clear();
+        if let Some(q) = &search {
+            params.append_pair("q", q);
+        }
+        if let Some(d) = from {
+            params.append_pair("from", &d.to_string());
+        }
+        if let Some(d) = to {
+            params.append_pair("to", &d.to_string());
+        }
+        params.append_pair("per_page", &per_page.to_string());
+        params.append_pair("page", &target.to_string());
+        drop(params);
+        format!("{}?{}", url.path(), url.query().unwrap_or_default())
+    };
+    if current > 1 {
+        body.push_str(&format!("<a href=\"{}\">&larr; Newer</a> ", escape(&link(current - 1))));
+    }
+    if current < pages {
+        body.push_str(&format!("<a href=\"{}\">Older &rarr;</a>", escape(&link(current + 1))));
+    }
+    body.push_str("</p>");
+
+    Ok(page(&req, "Logs", &body))
+}
+
+fn page(req: &Request<State>, title: &str, body: &str) -> Response {
+    let html = format!(
+        "<!doctype html><html><head><meta charset=\"utf-8\"><title>{title}</title>\
+        <style>body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse}}\
+        td,th{{border:1px solid #ccc;padding:4px 8px;text-align:left;vertical-align:top;white-space:pre-wrap}}</style>\
+        </head><body><nav><a href=\"/dashboard\">Home</a> | <a href=\"/dashboard/translations\">Translations</a> | \
+        <a href=\"/dashboard/logs\">Logs</a></nav><h1>{title}</h1>{body}</body></html>"
+    );
+    let mut response = Response::builder(StatusCode::Ok)
+        .body(html)
+        .content_type(mime::HTML)
+        .build();
+    // Remember a token passed in the url so links between pages keep working
+    if let Some(token) = auth::query_token(req) {
+        response.insert_cookie(auth::token_cookie(&token));
+    }
+    response
+}
+
+fn unauthorized() -> Response {
+    Response::builder(StatusCode::Unauthorized)
+        .body("Missing or invalid access token")
+        .build()
+}
+
+fn guild_name(state: &State, id: u64) -> String {
+    state
+        .cache
+        .guild(GuildId::new(id))
+        .map(|g| g.name.clone())
+        .unwrap_or_else(|| id.to_string())
+}
+
+fn option(value: &str, label: &str, selected: bool) -> String {
+    format!(
+        "<option value=\"{}\"{}>{}</option>",
+        escape(value),
+        if selected { " selected" } else { "" },
+        escape(label)
+    )
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn escape(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
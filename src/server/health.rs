@@ -0,0 +1,95 @@
+use crate::{
+    server::State,
+    utils::{db::storage_writable, metrics},
+};
+use poise::serenity_prelude as serenity;
+use serde::Serialize;
+use tide::{Body, Request, Response, StatusCode};
+
+#[derive(Serialize)]
+struct ShardStatus {
+    id: u32,
+    stage: String,
+    latency_ms: Option<u128>,
+    #[serde(skip)]
+    connected: bool,
+}
+
+#[derive(Serialize)]
+struct Status {
+    uptime_seconds: u64,
+    ready: bool,
+    guilds: usize,
+    shards: Vec<ShardStatus>,
+    commands: std::collections::BTreeMap<String, u64>,
+}
+
+async fn shard_statuses(state: &State) -> Vec<ShardStatus> {
+    let runners = state.shard_manager.runners.lock().await;
+    let mut shards: Vec<ShardStatus> = runners
+        .iter()
+        .map(|(id, info)| ShardStatus {
+            id: id.0,
+            stage: info.stage.to_string(),
+            latency_ms: info.latency.map(|l| l.as_millis()),
+            connected: info.stage == serenity::ConnectionStage::Connected,
+        })
+        .collect();
+    shards.sort_by_key(|s| s.id);
+    shards
+}
+
+/// Liveness: the process is up and serving requests
+pub async fn healthz(_req: Request<State>) -> tide::Result {
+    Ok(Response::builder(StatusCode::Ok).body("ok").build())
+}
+
+/// Readiness: the gateway is connected and storage can be written
+pub async fn readyz(req: Request<State>) -> tide::Result {
+    let state = req.state();
+    let shards = shard_statuses(state).await;
+    let connected = !shards.is_empty() && shards.iter().all(|s| s.connected);
+
+    let mut problems = Vec::new();
+    if !state.stats.is_ready() || !connected {
+        problems.push("gateway not connected");
+    }
+    let writable = state
+        .on_runtime(async { tokio::task::spawn_blocking(storage_writable).await })
+        .await?
+        .unwrap_or(false);
+    if !writable {
+        problems.push("storage not writable");
+    }
+
+    if problems.is_empty() {
+        Ok(Response::builder(StatusCode::Ok).body("ready").build())
+    } else {
+        Ok(Response::builder(StatusCode::ServiceUnavailable)
+            .body(problems.join(", "))
+            .build())
+    }
+}
+
+/// Uptime, guild count, shard latency and command counters as json
+pub async fn status(req: Request<State>) -> tide::Result {
+    let state = req.state();
+    let status = Status {
+        uptime_seconds: state.stats.uptime().as_secs(),
+        ready: state.stats.is_ready(),
+        guilds: state.cache.guilds().len(),
+        shards: shard_statuses(state).await,
+        commands: state.stats.command_counts(),
+    };
+    Ok(Response::builder(StatusCode::Ok)
+        .body(Body::from_json(&status)?)
+        .build())
+}
+
+/// Prometheus scrape endpoint
+pub async fn metrics(_req: Request<State>) -> tide::Result {
+    Ok(Response::builder(StatusCode::Ok)
+        .body(metrics::gather())
+        .content_type("text/plain; version=0.0.4")
+        .build())
+}
@@ -0,0 +1,70 @@
+use crate::{types::stats::Stats, utils::env};
+use poise::serenity_prelude::{self as serenity, Cache, Http};
+use std::{future::Future, sync::Arc};
+use tide::StatusCode;
+
+pub mod api;
+pub mod auth;
+pub mod dashboard;
+pub mod health;
+pub mod webhooks;
+
+/// Everything the http handlers need to look at the running bot
+#[derive(Clone)]
+pub struct State {
+    pub stats: Arc<Stats>,
+    pub http: Arc<Http>,
+    pub cache: Arc<Cache>,
+    pub shard_manager: Arc<serenity::ShardManager>,
+    pub runtime: tokio::runtime::Handle,
+}
+
+impl State {
+    /// Run a future on the bot's tokio runtime.
+    /// Tide handlers run on async-std, which serenity's http client can't use directly.
+    pub async fn on_runtime<F, T>(&self, future: F) -> tide::Result<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.runtime
+            .spawn(future)
+            .await
+            .map_err(|e| tide::Error::from_str(StatusCode::InternalServerError, e.to_string()))
+    }
+}
+
+pub fn start(state: State) {
+    let mut app = tide::with_state(state);
+    app.at("/healthz").get(health::healthz);
+    app.at("/readyz").get(health::readyz);
+    app.at("/status").get(health::status);
+    app.at("/metrics").get(health::metrics);
+    app.at("/dashboard").get(dashboard::index);
+    app.at("/dashboard/translations").get(dashboard::translations);
+    app.at("/dashboard/logs").get(dashboard::logs);
+
+    let mut api = tide::with_state(app.state().clone());
+    api.with(api::require_token);
+    api.at("/messages").post(api::post_message);
+    api.at("/translations")
+        .get(api::list_translations)
+        .post(api::add_translation);
+    api.at("/translations/:abbreviation")
+        .delete(api::delete_translation);
+    api.at("/realtime")
+        .get(api::get_realtime)
+        .put(api::put_realtime);
+    api.at("/logs").get(api::get_logs);
+    app.at("/api").nest(api);
+
+    app.at("/webhooks/:route").post(webhooks::relay);
+
+    let address = format!("{}:{}", env::http_host(), env::http_port());
+    tokio::spawn(async move {
+        println!("HTTP server listening on {}", address);
+        if let Err(e) = app.listen(address).await {
+            println!("HTTP server error: {}", e);
+        }
+    });
+}
@@ -0,0 +1,85 @@
+use crate::{
+    server::State,
+    utils::{
+        bot::send_embed,
+        log::log,
+        webhooks::{load_webhook_config, render_webhook, verify_signature},
+    },
+};
+use serde_json::{json, Value};
+use tide::{Body, Request, Response, StatusCode};
+
+/// Largest webhook body accepted. Bodies are read in full before the signature can be checked,
+/// so this bounds what an unsigned request can make the bot buffer.
+const MAX_BODY: usize = 1024 * 1024;
+
+/// POST /webhooks/:route
+pub async fn relay(mut req: Request<State>) -> tide::Result {
+    let name = req.param("route")?.to_string();
+    let config = load_webhook_config()
+        .map_err(|e| tide::Error::from_str(StatusCode::InternalServerError, e.to_string()))?;
+    let Some(route) = config.routes.get(&name) else {
+        return Ok(Response::builder(StatusCode::NotFound)
+            .body("unknown webhook route")
+            .build());
+    };
+
+    if req.len().is_some_and(|len| len > MAX_BODY) {
+        return Ok(too_large());
+    }
+    // Content-Length can be left out, so stop reading one byte past the limit regardless
+    let body = Body::from_reader(req.take_body(), Some(MAX_BODY + 1))
+        .into_bytes()
+        .await?;
+    if body.len() > MAX_BODY {
+        return Ok(too_large());
+    }
+
+    let signature = req
+        .header(route.signature_header.as_str())
+        .map(|h| h.as_str().to_string())
+        .unwrap_or_default();
+    if !verify_signature(&route.secret, &body, &signature) {
+        log(format!(
+            "Rejected webhook for route {}: bad signature",
+            name
+        ));
+        return Ok(Response::builder(StatusCode::Unauthorized)
+            .body("invalid signature")
+            .build());
+    }
+
+    // Non-json bodies are still relayed, available to templates as {{body}}
+    let payload: Value = serde_json::from_slice(&body)
+        .unwrap_or_else(|_| json!({ "body": String::from_utf8_lossy(&body) }));
+    let (content, embed) = render_webhook(&route.template, &payload);
+
+    let http = req.state().http.clone();
+    let channel_id = route.channel_id;
+    let result = req
+        .state()
+        .on_runtime(async move { send_embed(&http, channel_id, content, embed).await })
+        .await?;
+
+    match result {
+        Ok(_) => {
+            log(format!(
+                "Relayed webhook {} to channel {}",
+                name, channel_id
+            ));
+            Ok(Response::new(StatusCode::NoContent))
+        }
+        Err(e) => {
+            log(format!("Failed to relay webhook {}: {}", name, e));
+            Ok(Response::builder(StatusCode::BadGateway)
+                .body("failed to post to discord")
+                .build())
+        }
+    }
+}
+
+fn too_large() -> Response {
+    Response::builder(StatusCode::PayloadTooLarge)
+        .body("webhook body too large")
+        .build()
+}
@@ -0,0 +1,74 @@
+use crate::{
+    types::joke::JokeStore,
+    utils::{env, jokes::reload_jokes, log::log},
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+const TICK: Duration = Duration::from_secs(10);
+
+/// Watch everything under `STATIC_PATH` and reload the content when a file is added, removed or changed.
+/// Joke files are the only static content so far.
+pub fn start(jokes: Arc<JokeStore>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TICK);
+        let mut loaded = fingerprint();
+        let mut changing = None;
+        loop {
+            interval.tick().await;
+            let current = fingerprint();
+            if current == loaded {
+                changing = None;
+                continue;
+            }
+            // Only reload once nothing changed for a whole tick, so a file that is still
+            // being written isn't loaded half way
+            if changing.as_ref() != Some(&current) {
+                changing = Some(current);
+                continue;
+            }
+            changing = None;
+            // Remember broken files too, so the same error isn't logged every tick
+            loaded = current;
+            match reload_jokes(&jokes) {
+                Ok((collections, count)) => log(format!(
+                    "Static content changed, reloaded {} jokes in {} collections",
+                    count, collections
+                )),
+                Err(e) => log(format!(
+                    "Static content changed but didn't load, keeping the current version: {}",
+                    e
+                )),
+            }
+        }
+    });
+}
+
+type Fingerprint = Vec<(PathBuf, u64, Option<SystemTime>)>;
+
+/// Path, size and modification time of every file under `STATIC_PATH`
+fn fingerprint() -> Fingerprint {
+    let mut files = Vec::new();
+    add_files(Path::new(&env::static_path()), &mut files);
+    files.sort();
+    files
+}
+
+fn add_files(dir: &Path, files: &mut Fingerprint) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            add_files(&entry.path(), files);
+        } else {
+            files.push((entry.path(), metadata.len(), metadata.modified().ok()));
+        }
+    }
+}
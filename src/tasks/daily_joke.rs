@@ -0,0 +1,121 @@
+use crate::{
+    types::{
+        guild_settings::{DailyJoke, GuildSettings},
+        joke::{JokeLibrary, JokeStore},
+        types::Error,
+    },
+    utils::{
+        bot::send,
+        daily_jokes::{last_posted, mark_posted},
+        guild_settings::all_guild_settings,
+        joke_messages::render_joke,
+        jokes::{next_joke, resolve_collection},
+        log::log,
+        time::{guild_timezone, local_to_utc},
+    },
+};
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+use rand::seq::IndexedRandom;
+use std::{sync::Arc, time::Duration};
+
+const TICK: Duration = Duration::from_secs(60);
+
+pub fn start(ctx: serenity::Context, jokes: Arc<JokeStore>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TICK);
+        loop {
+            interval.tick().await;
+            for (guild_id, settings) in all_guild_settings() {
+                if let Some(daily) = &settings.daily_joke {
+                    post_if_due(&ctx, &jokes.current(), guild_id, &settings, daily).await;
+                }
+            }
+        }
+    });
+}
+
+/// Post today's joke once the guild's local time passes the configured time.
+/// The day is marked once a joke has been picked and before posting, so a crash mid-post
+/// can't lead to a second joke while a failure to pick one is retried on the next tick.
+async fn post_if_due(
+    ctx: &serenity::Context,
+    jokes: &JokeLibrary,
+    guild_id: u64,
+    settings: &GuildSettings,
+    daily: &DailyJoke,
+) {
+    let timezone = guild_timezone(guild_id);
+    let now = Utc::now().with_timezone(&timezone);
+    let today = now.date_naive();
+    if now.time() < daily.time || last_posted(guild_id) == Some(today) {
+        return;
+    }
+    // Set up after today's time had passed: the first joke is tomorrow's
+    let due = local_to_utc(today.and_time(daily.time), timezone);
+    if daily
+        .configured_at
+        .zip(due)
+        .is_some_and(|(at, due)| at > due)
+    {
+        return;
+    }
+
+    // A collection that was turned off since falls back to the guild's default
+    let name = daily
+        .collection
+        .as_deref()
+        .filter(|name| !settings.disabled_joke_collections.iter().any(|c| c == name));
+    let picked = resolve_collection(jokes, Some(guild_id), name).and_then(|collection| {
+        // One rotation per guild, separate from the channels' own /joke rotations
+        let rotation = format!("daily:{}", guild_id);
+        let joke = next_joke(&collection, &rotation, Some(guild_id), daily.with_remark)?;
+        Ok(joke.map(|joke| (collection, joke)))
+    });
+    let (collection, joke) = match picked {
+        Ok(Some(picked)) => picked,
+        // Storage hiccups get another try next tick
+        Err(e @ Error::Storage(_)) => {
+            log(format!("No joke of the day for {} yet: {}", guild_id, e));
+            return;
+        }
+        // Nothing to pick from won't fix itself today, so give up until tomorrow
+        other => {
+            let reason = match other {
+                Err(e) => e.to_string(),
+                _ => "nothing to pick from".to_string(),
+            };
+            log(format!("No joke of the day for {}: {}", guild_id, reason));
+            let _ = mark_posted(guild_id, today);
+            return;
+        }
+    };
+    if let Err(e) = mark_posted(guild_id, today) {
+        log(format!(
+            "Could not record joke of the day for {}: {}",
+            guild_id, e
+        ));
+        return;
+    }
+    let remark = daily
+        .with_remark
+        .then(|| joke.remarks.choose(&mut rand::rng()))
+        .flatten();
+
+    for channel_id in &daily.channels {
+        let builder = render_joke(
+            &collection,
+            &joke,
+            remark.map(String::as_str),
+            Some("Joke of the day"),
+            settings.plain_jokes,
+        )
+        .into_message();
+        if let Err(e) = send(&ctx.http, *channel_id, builder).await {
+            log(format!(
+                "Joke of the day failed in <#{}>: {}",
+                channel_id, e
+            ));
+        }
+    }
+}
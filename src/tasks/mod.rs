@@ -0,0 +1,16 @@
+use crate::types::joke::JokeStore;
+use poise::serenity_prelude as serenity;
+use std::sync::Arc;
+
+pub mod content;
+pub mod daily_joke;
+pub mod reminders;
+pub mod scheduler;
+
+/// Start every background loop. Called once the bot is ready.
+pub fn start(ctx: &serenity::Context, jokes: Arc<JokeStore>) {
+    scheduler::start(ctx.clone());
+    reminders::start(ctx.clone());
+    daily_joke::start(ctx.clone(), jokes.clone());
+    content::start(jokes);
+}
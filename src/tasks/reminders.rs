@@ -0,0 +1,124 @@
+use crate::{
+    types::reminder::Reminder,
+    utils::{
+        bot::send,
+        log::log,
+        reminders::{load_reminders, remove_reminder, update_reminder},
+    },
+};
+use chrono::Utc;
+use poise::serenity_prelude::{
+    self as serenity, ButtonStyle, CreateActionRow, CreateAllowedMentions, CreateButton,
+    CreateMessage, UserId,
+};
+use std::time::Duration;
+
+const TICK: Duration = Duration::from_secs(15);
+const MAX_FAILURES: u32 = 5;
+/// How long a delivered reminder can still be snoozed
+const KEEP_DELIVERED: chrono::TimeDelta = chrono::TimeDelta::days(2);
+
+/// Snooze buttons offered on a delivered reminder, as (label, minutes)
+pub const SNOOZE_OPTIONS: [(&str, i64); 3] =
+    [("10 minutes", 10), ("1 hour", 60), ("tomorrow", 1440)];
+
+pub fn start(ctx: serenity::Context) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TICK);
+        loop {
+            interval.tick().await;
+            run_due(&ctx).await;
+        }
+    });
+}
+
+/// Deliver everything that is due and forget old delivered reminders
+async fn run_due(ctx: &serenity::Context) {
+    let now = Utc::now();
+    for reminder in load_reminders() {
+        if reminder.delivered {
+            if reminder.remind_at + KEEP_DELIVERED < now {
+                let _ = remove_reminder(reminder.id);
+            }
+            continue;
+        }
+        if reminder.remind_at > now {
+            continue;
+        }
+        // Apply the outcome to the reminder as it is now: it may have been cancelled or snoozed
+        // while it was being sent
+        let result = match deliver(ctx, &reminder).await {
+            Ok(()) => update_reminder(reminder.id, |r| {
+                if r.remind_at == reminder.remind_at {
+                    r.delivered = true;
+                    r.failures = 0;
+                }
+            }),
+            Err(e) => {
+                let failures = reminder.failures + 1;
+                log(format!(
+                    "Reminder #{} failed ({}/{}): {}",
+                    reminder.id, failures, MAX_FAILURES, e
+                ));
+                if failures >= MAX_FAILURES {
+                    log(format!("Giving up on reminder #{}", reminder.id));
+                    let _ = remove_reminder(reminder.id);
+                    continue;
+                }
+                update_reminder(reminder.id, |r| r.failures = failures)
+            }
+        };
+        if let Err(e) = result {
+            log(format!("Could not update reminder #{}: {}", reminder.id, e));
+        }
+    }
+}
+
+async fn deliver(
+    ctx: &serenity::Context,
+    reminder: &Reminder,
+) -> Result<(), crate::types::types::Error> {
+    let user = UserId::new(reminder.user_id);
+    let channel_id = if reminder.dm {
+        user.create_dm_channel(&ctx.http).await?.id.get()
+    } else {
+        reminder.channel_id
+    };
+    let builder = CreateMessage::new()
+        .content(reminder_content(reminder))
+        .components(snooze_buttons(reminder.id))
+        .allowed_mentions(CreateAllowedMentions::new().users(vec![user]));
+    send(&ctx.http, channel_id, builder).await?;
+    Ok(())
+}
+
+/// Text of a delivered reminder
+pub fn reminder_content(reminder: &Reminder) -> String {
+    let mut content = format!(
+        "<@{}> ⏰ {}\n-# Set <t:{}:R>",
+        reminder.user_id,
+        reminder.text,
+        reminder.created_at.timestamp()
+    );
+    if let Some(link) = &reminder.context_link {
+        content.push_str(&format!(" · [context]({})", link));
+    }
+    content
+}
+
+fn snooze_buttons(id: u64) -> Vec<CreateActionRow> {
+    let buttons = SNOOZE_OPTIONS
+        .iter()
+        .map(|(label, minutes)| {
+            CreateButton::new(format!("reminder:snooze:{}:{}", id, minutes))
+                .label(format!("Snooze {}", label))
+                .style(ButtonStyle::Secondary)
+        })
+        .chain(std::iter::once(
+            CreateButton::new(format!("reminder:done:{}", id))
+                .label("Done")
+                .style(ButtonStyle::Success),
+        ))
+        .collect();
+    vec![CreateActionRow::Buttons(buttons)]
+}
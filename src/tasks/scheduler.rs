@@ -0,0 +1,116 @@
+use crate::{
+    types::{schedule::ScheduledMessage, types::Error},
+    utils::{
+        bot::send,
+        log::log,
+        relay::{check_relay_target, sanitised_mentions},
+        schedules::{
+            load_schedules, next_cron_run, read_schedule_file, remove_schedule, update_schedule,
+        },
+        time::guild_timezone,
+    },
+};
+use chrono::Utc;
+use poise::serenity_prelude::{
+    self as serenity, ChannelId, CreateAttachment, CreateMessage, GuildId, UserId,
+};
+use std::time::Duration;
+
+const TICK: Duration = Duration::from_secs(20);
+const MAX_FAILURES: u32 = 5;
+
+pub fn start(ctx: serenity::Context) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TICK);
+        loop {
+            interval.tick().await;
+            run_due(&ctx).await;
+        }
+    });
+}
+
+/// Post everything that is due. Messages missed while the bot was down go out once on the next tick.
+async fn run_due(ctx: &serenity::Context) {
+    let now = Utc::now();
+    for schedule in load_schedules().into_iter().filter(|s| s.next_run <= now) {
+        let result = match check_author(ctx, &schedule).await {
+            // Relaying may have been switched off, or the author lost access, since the message was scheduled
+            Err(e) if e.is_user_error() => {
+                log(format!("Skipped scheduled message #{}: {}", schedule.id, e));
+                reschedule(schedule);
+                continue;
+            }
+            Err(e) => Err(e),
+            Ok(()) => post(ctx, &schedule).await,
+        };
+        match result {
+            Ok(()) => reschedule(schedule),
+            Err(e) => {
+                let failures = schedule.failures + 1;
+                log(format!(
+                    "Scheduled message #{} failed ({}/{}): {}",
+                    schedule.id, failures, MAX_FAILURES, e
+                ));
+                if failures >= MAX_FAILURES {
+                    log(format!("Giving up on scheduled message #{}", schedule.id));
+                    reschedule(schedule);
+                } else {
+                    let _ = update_schedule(schedule.id, |s| s.failures = failures);
+                }
+            }
+        }
+    }
+}
+
+/// The checks the message went through when it was scheduled, as things are now
+async fn check_author(ctx: &serenity::Context, schedule: &ScheduledMessage) -> Result<(), Error> {
+    let author = UserId::new(schedule.author_id).to_user(ctx).await?;
+    check_relay_target(
+        ctx,
+        Some(GuildId::new(schedule.guild_id)),
+        &author,
+        ChannelId::new(schedule.channel_id),
+    )
+    .await?;
+    Ok(())
+}
+
+async fn post(ctx: &serenity::Context, schedule: &ScheduledMessage) -> Result<(), Error> {
+    let mut builder = CreateMessage::new().allowed_mentions(sanitised_mentions());
+    if !schedule.content.is_empty() {
+        builder = builder.content(&schedule.content);
+    }
+    if let Some(embed) = &schedule.embed {
+        builder = builder.embed(embed.build());
+    }
+    for file in &schedule.files {
+        builder = builder.add_file(CreateAttachment::bytes(
+            read_schedule_file(file)?,
+            file.filename.clone(),
+        ));
+    }
+    send(&ctx.http, schedule.channel_id, builder).await?;
+    Ok(())
+}
+
+/// Move a recurring message to its next run, or drop a one-off.
+/// A message removed while it was being sent stays removed.
+fn reschedule(schedule: ScheduledMessage) {
+    let next = schedule
+        .cron
+        .as_deref()
+        .and_then(|cron| next_cron_run(cron, guild_timezone(schedule.guild_id), Utc::now()));
+    let result = match next {
+        Some(next) => update_schedule(schedule.id, |s| {
+            s.next_run = next;
+            s.failures = 0;
+        }),
+        None => remove_schedule(schedule.id),
+    };
+    if let Err(e) = result {
+        log(format!(
+            "Could not update scheduled message #{}: {}",
+            schedule.id, e
+        ));
+    }
+}
@@ -0,0 +1,44 @@
+/// A running cooldown and the invocation that started it
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct ActiveCooldown {
+    /// Unix timestamp the cooldown runs out at
+    pub expires: i64,
+    pub invocation: u64,
+}
+
+/// How long a command stays on cooldown in each scope, in seconds
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CooldownRule {
+    pub user: Option<u64>,
+    pub channel: Option<u64>,
+    pub guild: Option<u64>,
+}
+
+impl CooldownRule {
+    /// Built-in cooldowns for the commands most likely to be spammed
+    pub fn default_for(command: &str) -> Option<Self> {
+        let rule = match command {
+            "say" | "echo" => CooldownRule {
+                user: Some(30),
+                channel: Some(10),
+                guild: None,
+            },
+            "joke tell" | "remark" => CooldownRule {
+                user: Some(5),
+                channel: Some(3),
+                guild: None,
+            },
+            "translate" => CooldownRule {
+                user: Some(10),
+                channel: Some(5),
+                guild: None,
+            },
+            _ => return None,
+        };
+        Some(rule)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.user.is_none() && self.channel.is_none() && self.guild.is_none()
+    }
+}
@@ -0,0 +1,63 @@
+use poise::serenity_prelude as serenity;
+
+/// Everything that can go wrong in a command, event or storage call
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Reading or writing a pickledb file failed
+    #[error("storage error: {0}")]
+    Storage(String),
+    /// Discord rejected or failed a request
+    #[error("discord error: {0}")]
+    Discord(Box<serenity::Error>),
+    /// The user's input doesn't make sense
+    #[error("{0}")]
+    Validation(String),
+    /// The user asked for something that doesn't exist
+    #[error("{0}")]
+    NotFound(String),
+    /// The user isn't allowed to do this
+    #[error("{0}")]
+    Permission(String),
+    /// The command is cooling down until this unix timestamp
+    #[error("Slow down! You can use this command again <t:{0}:R>.")]
+    Cooldown(i64),
+    /// A static content file is malformed
+    #[error("{file}:{line}: {message}")]
+    Content {
+        file: String,
+        line: usize,
+        message: String,
+    },
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl Error {
+    /// Errors caused by the user, safe to show them verbatim
+    pub fn is_user_error(&self) -> bool {
+        matches!(
+            self,
+            Error::Validation(_) | Error::NotFound(_) | Error::Permission(_) | Error::Cooldown(_)
+        )
+    }
+}
+
+impl From<serenity::Error> for Error {
+    fn from(e: serenity::Error) -> Self {
+        Error::Discord(Box::new(e))
+    }
+}
+
+impl From<pickledb::error::Error> for Error {
+    fn from(e: pickledb::error::Error) -> Self {
+        Error::Storage(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Internal(format!("json error: {}", e))
+    }
+}
@@ -0,0 +1,46 @@
+use crate::types::permission::PermissionLevel;
+use chrono::{DateTime, NaiveTime, Utc};
+use std::collections::HashMap;
+
+/// Per-guild configuration, stored as one entry per guild
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct GuildSettings {
+    /// Roles whose members count as bot admins in this guild
+    pub admin_roles: Vec<u64>,
+    /// Overrides of the level each command requires, keyed by command name
+    pub command_levels: HashMap<String, PermissionLevel>,
+    /// Kill switch for /say and echo relaying
+    pub relay_disabled: bool,
+    /// IANA timezone name used for schedules, UTC if unset
+    pub timezone: Option<String>,
+    /// Joke collections turned off in this guild; new collections start enabled
+    pub disabled_joke_collections: Vec<String>,
+    /// Collection used when a joke command doesn't name one
+    pub default_joke_collection: Option<String>,
+    /// Channel new joke submissions are posted to for review
+    pub joke_review_channel: Option<u64>,
+    /// Let better rated jokes come up earlier in each channel's rotation
+    pub weight_jokes_by_rating: bool,
+    /// Post jokes in the old plain text format instead of embeds
+    pub plain_jokes: bool,
+    /// Joke of the day posting, off if unset
+    pub daily_joke: Option<DailyJoke>,
+}
+
+/// When and where a guild gets its joke of the day
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DailyJoke {
+    pub channels: Vec<u64>,
+    /// Local time in the guild's timezone
+    pub time: NaiveTime,
+    /// Post a remark under the joke, only picking jokes that have one
+    #[serde(default)]
+    pub with_remark: bool,
+    /// Collection to draw from, the guild's default if unset
+    #[serde(default)]
+    pub collection: Option<String>,
+    /// When the schedule was last changed; a time that had already passed then waits for tomorrow
+    #[serde(default)]
+    pub configured_at: Option<DateTime<Utc>>,
+}
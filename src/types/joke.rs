@@ -0,0 +1,131 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, RwLock},
+};
+
+/// One joke, with whatever remarks have been made about it
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Joke {
+    /// Stable number shown to users and used to ask for this joke again
+    pub id: u32,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remarks: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Where the joke came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// The jokes from one file, indexed by number
+#[derive(Debug, Default)]
+pub struct JokeCollection {
+    /// File name without extension, used to pick the collection in commands
+    pub name: String,
+    /// Display name, the file's `title` if it has one
+    pub title: String,
+    /// What ratings, favourites and buttons call the collection: the name, qualified with
+    /// the guild for collections that only exist in one guild
+    pub key: String,
+    jokes: Vec<Joke>,
+    index: HashMap<u32, usize>,
+}
+
+impl JokeCollection {
+    pub fn new(name: String, title: String, jokes: Vec<Joke>) -> Self {
+        let index = jokes
+            .iter()
+            .enumerate()
+            .map(|(position, joke)| (joke.id, position))
+            .collect();
+        JokeCollection {
+            key: name.clone(),
+            name,
+            title,
+            jokes,
+            index,
+        }
+    }
+
+    /// Give the collection a key of its own, so its joke numbers don't clash with the same
+    /// collection elsewhere
+    pub fn with_key(mut self, key: String) -> Self {
+        self.key = key;
+        self
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Joke> {
+        self.index.get(&id).map(|&position| &self.jokes[position])
+    }
+
+    /// Numbers of every joke, optionally only those with remarks
+    pub fn ids(&self, with_remark: bool) -> Vec<u32> {
+        self.jokes
+            .iter()
+            .filter(|joke| !with_remark || !joke.remarks.is_empty())
+            .map(|joke| joke.id)
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.jokes.len()
+    }
+
+    /// How many jokes have at least one remark
+    pub fn with_remarks(&self) -> usize {
+        self.jokes
+            .iter()
+            .filter(|joke| !joke.remarks.is_empty())
+            .count()
+    }
+}
+
+/// Every loaded joke collection, by name
+#[derive(Debug, Default)]
+pub struct JokeLibrary {
+    collections: BTreeMap<String, Arc<JokeCollection>>,
+}
+
+impl JokeLibrary {
+    pub fn new(collections: Vec<JokeCollection>) -> Self {
+        JokeLibrary {
+            collections: collections
+                .into_iter()
+                .map(|collection| (collection.name.clone(), Arc::new(collection)))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Arc<JokeCollection>> {
+        self.collections.get(name)
+    }
+
+    /// Collections in name order
+    pub fn collections(&self) -> impl Iterator<Item = &Arc<JokeCollection>> {
+        self.collections.values()
+    }
+}
+
+/// The loaded jokes, swapped out as a whole when the files are reloaded
+#[derive(Debug, Default)]
+pub struct JokeStore {
+    current: RwLock<Arc<JokeLibrary>>,
+}
+
+impl JokeStore {
+    pub fn new(library: JokeLibrary) -> Self {
+        JokeStore {
+            current: RwLock::new(Arc::new(library)),
+        }
+    }
+
+    /// The jokes as of now; holding on to them doesn't block a reload
+    pub fn current(&self) -> Arc<JokeLibrary> {
+        self.current.read().unwrap().clone()
+    }
+
+    pub fn replace(&self, library: JokeLibrary) {
+        *self.current.write().unwrap() = Arc::new(library);
+    }
+}
@@ -1,3 +1,15 @@
+pub mod cooldown;
 pub mod dblog;
+pub mod error;
+pub mod guild_settings;
+pub mod joke;
+pub mod permission;
+pub mod rating;
+pub mod reminder;
+pub mod schedule;
+pub mod stats;
+pub mod submission;
 pub mod translation;
+#[allow(clippy::module_inception)]
 pub mod types;
+pub mod webhook;
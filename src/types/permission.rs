@@ -0,0 +1,31 @@
+/// Who may run a command, from least to most privileged
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    poise::ChoiceParameter,
+)]
+pub enum PermissionLevel {
+    Everyone,
+    Admin,
+    Owner,
+}
+
+impl PermissionLevel {
+    /// Level a command needs when the guild hasn't configured one
+    pub fn default_for(command: &str) -> Self {
+        match command {
+            "say" | "echo" | "schedule" | "realtime" | "relay" | "logs" | "permissions" => {
+                PermissionLevel::Admin
+            }
+            "admin" => PermissionLevel::Owner,
+            _ => PermissionLevel::Everyone,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+/// One user's vote on a joke
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct Vote {
+    /// +1 or -1
+    pub value: i8,
+    /// Guild the vote was cast in, for per-server leaderboards
+    pub guild_id: Option<u64>,
+}
+
+/// Every vote on one joke, keyed by user id
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JokeRating {
+    pub votes: HashMap<u64, Vote>,
+}
+
+impl JokeRating {
+    /// Upvotes and downvotes, only counting votes from `guild_id` if given
+    pub fn counts(&self, guild_id: Option<u64>) -> (u32, u32) {
+        self.votes
+            .values()
+            .filter(|vote| guild_id.is_none() || vote.guild_id == guild_id)
+            .fold((0, 0), |(up, down), vote| {
+                if vote.value > 0 {
+                    (up + 1, down)
+                } else {
+                    (up, down + 1)
+                }
+            })
+    }
+
+    pub fn score(&self, guild_id: Option<u64>) -> i64 {
+        let (up, down) = self.counts(guild_id);
+        up as i64 - down as i64
+    }
+}
+
+/// Which votes a leaderboard counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum LeaderboardScope {
+    #[name = "This server"]
+    Server,
+    Overall,
+}
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+
+/// A personal reminder set with /remind
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Reminder {
+    pub id: u64,
+    pub user_id: u64,
+    pub guild_id: Option<u64>,
+    /// Channel the reminder was set in, and where it is delivered unless `dm` is set
+    pub channel_id: u64,
+    /// Link back to the message the reminder was set from
+    pub context_link: Option<String>,
+    pub text: String,
+    pub remind_at: DateTime<Utc>,
+    pub dm: bool,
+    pub created_at: DateTime<Utc>,
+    /// Delivered reminders are kept for a while so their snooze buttons keep working
+    #[serde(default)]
+    pub delivered: bool,
+    #[serde(default)]
+    pub failures: u32,
+}
+
+impl Reminder {
+    pub fn display(&self) -> String {
+        let preview: String = self.text.chars().take(60).collect();
+        let place = if self.dm {
+            "by DM".to_string()
+        } else {
+            format!("in <#{}>", self.channel_id)
+        };
+        format!(
+            "**#{}** <t:{}:R> {}: {}",
+            self.id,
+            self.remind_at.timestamp(),
+            place,
+            preview
+        )
+    }
+}
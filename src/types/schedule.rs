@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use poise::serenity_prelude::CreateEmbed;
+
+/// A message the bot posts at a set time, once or on a cron schedule
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScheduledMessage {
+    pub id: u64,
+    pub guild_id: u64,
+    pub channel_id: u64,
+    pub author_id: u64,
+    pub content: String,
+    pub next_run: DateTime<Utc>,
+    /// Cron expression for recurring messages, evaluated in the guild's timezone
+    pub cron: Option<String>,
+    #[serde(default)]
+    pub failures: u32,
+    #[serde(default)]
+    pub embed: Option<ScheduledEmbed>,
+    /// Files to attach, kept on disk until the message is posted
+    #[serde(default)]
+    pub files: Vec<ScheduledFile>,
+}
+
+/// An embed posted with a scheduled message
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScheduledEmbed {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub colour: Option<u32>,
+}
+
+impl ScheduledEmbed {
+    pub fn build(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::new();
+        if let Some(title) = &self.title {
+            embed = embed.title(title);
+        }
+        if let Some(description) = &self.description {
+            embed = embed.description(description);
+        }
+        if let Some(colour) = self.colour {
+            embed = embed.colour(colour);
+        }
+        embed
+    }
+}
+
+/// A file attached to a scheduled message. `path` is under `DATA_PATH/scheduled/`;
+/// `filename` is what it is posted as.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScheduledFile {
+    pub path: String,
+    pub filename: String,
+}
+
+impl ScheduledMessage {
+    pub fn display(&self) -> String {
+        let when = match &self.cron {
+            Some(cron) => format!("`{}` (next <t:{}:f>)", cron, self.next_run.timestamp()),
+            None => format!("<t:{}:f>", self.next_run.timestamp()),
+        };
+        let mut preview: String = self.content.chars().take(60).collect();
+        if self.embed.is_some() {
+            preview.push_str(" [embed]");
+        }
+        for file in &self.files {
+            preview.push_str(&format!(" [file: {}]", file.filename));
+        }
+        format!(
+            "**#{}** in <#{}> {}: {}",
+            self.id, self.channel_id, when, preview
+        )
+    }
+}
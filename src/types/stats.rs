@@ -0,0 +1,47 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// Process-wide counters shared between the bot and the http server
+pub struct Stats {
+    started: Instant,
+    ready: AtomicBool,
+    commands: Mutex<HashMap<String, u64>>,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats {
+            started: Instant::now(),
+            ready: AtomicBool::new(false),
+            commands: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn set_ready(&self, ready: bool) {
+        self.ready.store(ready, Ordering::Relaxed);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Relaxed)
+    }
+
+    pub fn record_command(&self, name: &str) {
+        let mut commands = self.commands.lock().unwrap();
+        *commands.entry(name.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn command_counts(&self) -> BTreeMap<String, u64> {
+        let commands = self.commands.lock().unwrap();
+        commands.iter().map(|(k, v)| (k.clone(), *v)).collect()
+    }
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SubmissionStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// A joke sent in with /joke submit, waiting for or past moderation
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JokeSubmission {
+    pub id: u64,
+    pub guild_id: u64,
+    pub author_id: u64,
+    pub text: String,
+    pub remark: Option<String>,
+    pub status: SubmissionStatus,
+    pub submitted_at: DateTime<Utc>,
+    pub reviewed_by: Option<u64>,
+    /// Number in the guild's community collection, once approved
+    pub joke_id: Option<u32>,
+}
+
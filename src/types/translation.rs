@@ -2,4 +2,6 @@
 pub struct Translation {
     pub abbreviation: String,
     pub definition: String,
+    /// Guild the translation was picked up in, if known
+    pub guild_id: Option<u64>,
 }
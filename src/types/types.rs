@@ -1,9 +1,14 @@
+use crate::types::{joke::JokeStore, stats::Stats};
 use pickledb::PickleDb;
+use std::sync::Arc;
 use tokio::sync::Mutex;
 
+pub use crate::types::error::Error;
+
 pub struct Data {
-    pub _db: Mutex<PickleDb>,
+    pub db: Mutex<PickleDb>,
+    pub stats: Arc<Stats>,
+    pub jokes: Arc<JokeStore>,
 } // User data, which is stored and accessible in all command invocations
-pub type Error = Box<dyn std::error::Error + Send + Sync>;
 pub type Context<'a> = poise::Context<'a, Data, Error>;
-pub type AppContext<'a> = poise::ApplicationContext<'a, Data, Error>;
\ No newline at end of file
+pub type AppContext<'a> = poise::ApplicationContext<'a, Data, Error>;
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+/// Contents of the webhook relay config file
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct WebhookConfig {
+    #[serde(default)]
+    pub routes: HashMap<String, WebhookRoute>,
+}
+
+/// One incoming webhook endpoint, relayed to a single channel
+#[derive(Debug, serde::Deserialize)]
+pub struct WebhookRoute {
+    pub channel_id: u64,
+    /// Shared secret used to verify the HMAC-SHA256 signature of the body. Required:
+    /// unsigned requests would let anyone post into the channel.
+    pub secret: String,
+    #[serde(default = "default_signature_header")]
+    pub signature_header: String,
+    #[serde(default)]
+    pub template: WebhookTemplate,
+}
+
+/// Embed layout; every string may contain `{{path.to.field}}` placeholders
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct WebhookTemplate {
+    pub content: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub color: Option<String>,
+    pub footer: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<WebhookField>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct WebhookField {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub inline: bool,
+}
+
+fn default_signature_header() -> String {
+    "X-Hub-Signature-256".to_string()
+}
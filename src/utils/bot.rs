@@ -1,18 +1,64 @@
-use crate::utils::env;
-use poise::serenity_prelude::{self as serenity, Http};
+use crate::{
+    types::types::Error,
+    utils::{env, metrics},
+};
+use poise::serenity_prelude::{
+    self as serenity, CreateAllowedMentions, CreateEmbed, CreateMessage, Http,
+};
 use serenity::model::id::ChannelId;
 
 pub fn send_message_in_channel(message: &str, channel_id: u64) {
-    let channel = ChannelId::new(channel_id);
     let http = Http::new(&env::discord_token());
 
     // Spawn a new Tokio task to send the message asynchronously
     let message = message.to_string();
     tokio::spawn(async move {
-        let _ = channel.say(&http, format!("{}", message)).await;
+        let _ = send_message(&http, channel_id, &message).await;
     });
 }
 
+/// Send a message and wait for Discord to accept it. Nothing in it can ping anyone.
+pub async fn send_message(
+    http: &Http,
+    channel_id: u64,
+    message: &str,
+) -> Result<serenity::Message, Error> {
+    let builder = CreateMessage::new()
+        .content(message)
+        .allowed_mentions(CreateAllowedMentions::new());
+    send(http, channel_id, builder).await
+}
+
+/// Send an embed, with optional text above it. Nothing in it can ping anyone.
+pub async fn send_embed(
+    http: &Http,
+    channel_id: u64,
+    content: Option<String>,
+    embed: Option<CreateEmbed>,
+) -> Result<serenity::Message, Error> {
+    let mut builder = CreateMessage::new().allowed_mentions(CreateAllowedMentions::new());
+    if let Some(content) = content {
+        builder = builder.content(content);
+    }
+    if let Some(embed) = embed {
+        builder = builder.embed(embed);
+    }
+    send(http, channel_id, builder).await
+}
+
+/// Every outbound message goes through here
+pub async fn send(
+    http: &Http,
+    channel_id: u64,
+    builder: CreateMessage,
+) -> Result<serenity::Message, Error> {
+    let channel = ChannelId::new(channel_id);
+    channel.send_message(http, builder).await.map_err(|e| {
+        metrics::OUTBOUND_FAILURES.inc();
+        Error::from(e)
+    })
+}
+
 //new functionality here
 
 //new comment
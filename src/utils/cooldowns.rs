@@ -0,0 +1,218 @@
+use crate::{
+    types::{
+        cooldown::{ActiveCooldown, CooldownRule},
+        permission::PermissionLevel,
+        types::{Context, Error},
+    },
+    utils::{db::create_or_open_db, env, log::log, metrics::time_storage, permissions::user_level},
+};
+use chrono::Utc;
+use poise::serenity_prelude::{ChannelId, GuildId, Message, UserId};
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+
+/// Rule for `command`: `COOLDOWNS` overrides the built-in defaults. Subcommands have their own rules.
+/// Format: `joke tell=user:10,channel:3;say=user:30,guild:5` (a rule of `say=off` disables it).
+pub fn cooldown_rule(command: &str) -> Option<CooldownRule> {
+    parse_cooldown_rule(&env::cooldowns(), command)
+}
+
+fn parse_cooldown_rule(config: &str, command: &str) -> Option<CooldownRule> {
+    for entry in config.split(';') {
+        let Some((name, scopes)) = entry.split_once('=') else {
+            continue;
+        };
+        if name.trim() != command {
+            continue;
+        }
+        let mut rule = CooldownRule::default();
+        for scope in scopes.split(',') {
+            let Some((scope, seconds)) = scope.split_once(':') else {
+                continue;
+            };
+            let seconds = seconds.trim().parse::<u64>().ok().filter(|s| *s > 0);
+            match scope.trim() {
+                "user" => rule.user = seconds,
+                "channel" => rule.channel = seconds,
+                "guild" => rule.guild = seconds,
+                _ => {}
+            }
+        }
+        return Some(rule).filter(|r| !r.is_empty());
+    }
+    CooldownRule::default_for(command)
+}
+
+/// The storage keys and lengths that apply to an invocation by `user_id` in `channel_id`
+fn cooldown_keys(
+    command: &str,
+    rule: &CooldownRule,
+    user_id: UserId,
+    channel_id: ChannelId,
+    guild_id: Option<GuildId>,
+) -> Vec<(String, u64)> {
+    let mut keys = Vec::new();
+    if let Some(seconds) = rule.user {
+        keys.push((format!("{}:user:{}", command, user_id), seconds));
+    }
+    if let Some(seconds) = rule.channel {
+        keys.push((format!("{}:channel:{}", command, channel_id), seconds));
+    }
+    if let (Some(seconds), Some(guild_id)) = (rule.guild, guild_id) {
+        keys.push((format!("{}:guild:{}", command, guild_id), seconds));
+    }
+    keys
+}
+
+/// Cooldowns apply per subcommand, so /joke tell doesn't hold up /joke list
+fn command_name(ctx: Context<'_>) -> String {
+    ctx.command().qualified_name.clone()
+}
+
+async fn bypasses_cooldowns(ctx: Context<'_>) -> bool {
+    user_level(ctx).await >= PermissionLevel::Admin
+}
+
+/// Poise runs the checks for every autocomplete keystroke too; only submitting the command counts
+fn is_autocomplete(ctx: Context<'_>) -> bool {
+    matches!(ctx, poise::Context::Application(ctx)
+        if ctx.interaction_type == poise::CommandInteractionType::Autocomplete)
+}
+
+/// Cooldowns that haven't run out, by storage key. Loaded once and saved back as a single entry,
+/// so starting a cooldown is one write however many are active.
+static ACTIVE: LazyLock<Mutex<HashMap<String, ActiveCooldown>>> = LazyLock::new(|| {
+    let db = create_or_open_db(env::data_path() + "cooldowns.db");
+    Mutex::new(db.get("active").unwrap_or_default())
+});
+
+/// Fail with `Error::Cooldown` if any scope of this command is still cooling down, otherwise start
+/// its cooldowns. Checking and starting happen under one lock, so two invocations can't both slip through.
+/// Poise runs the check once per parent command as well; the invocation's own cooldowns don't count against it.
+pub async fn claim_cooldown(ctx: Context<'_>) -> Result<(), Error> {
+    if is_autocomplete(ctx) {
+        return Ok(());
+    }
+    let command = command_name(ctx);
+    let Some(rule) = cooldown_rule(&command) else {
+        return Ok(());
+    };
+    if bypasses_cooldowns(ctx).await {
+        return Ok(());
+    }
+
+    let keys = cooldown_keys(
+        &command,
+        &rule,
+        ctx.author().id,
+        ctx.channel_id(),
+        ctx.guild_id(),
+    );
+    claim(keys, ctx.id())
+}
+
+/// [`claim_cooldown`] for commands typed as chat messages, like `echo '...' >> #channel`.
+/// Callers let admins through themselves, as they already know the author's level.
+pub fn claim_message_cooldown(command: &str, message: &Message) -> Result<(), Error> {
+    let Some(rule) = cooldown_rule(command) else {
+        return Ok(());
+    };
+    let keys = cooldown_keys(
+        command,
+        &rule,
+        message.author.id,
+        message.channel_id,
+        message.guild_id,
+    );
+    claim(keys, message.id.get())
+}
+
+/// Check and start the cooldowns behind `keys` for one invocation
+fn claim(keys: Vec<(String, u64)>, invocation: u64) -> Result<(), Error> {
+    let now = Utc::now().timestamp();
+    let mut active = ACTIVE.lock().unwrap();
+    active.retain(|_, cooldown| cooldown.expires > now);
+    let ready_at = keys
+        .iter()
+        .filter_map(|(key, _)| active.get(key))
+        .filter(|cooldown| cooldown.invocation != invocation)
+        .map(|cooldown| cooldown.expires)
+        .max();
+    if let Some(ready_at) = ready_at {
+        return Err(Error::Cooldown(ready_at));
+    }
+
+    for (key, seconds) in keys {
+        active.insert(
+            key,
+            ActiveCooldown {
+                expires: now + seconds as i64,
+                invocation,
+            },
+        );
+    }
+    let mut db = create_or_open_db(env::data_path() + "cooldowns.db");
+    if let Err(e) = time_storage("write", || db.set("active", &*active)) {
+        log(format!("Could not save cooldowns: {}", e));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_rules_override_defaults() {
+        let config = "joke tell=user:10,channel:3;say=user:30,guild:5";
+        assert_eq!(
+            parse_cooldown_rule(config, "joke tell"),
+            Some(CooldownRule {
+                user: Some(10),
+                channel: Some(3),
+                guild: None,
+            })
+        );
+        assert_eq!(
+            parse_cooldown_rule(config, "say"),
+            Some(CooldownRule {
+                user: Some(30),
+                channel: None,
+                guild: Some(5),
+            })
+        );
+    }
+
+    #[test]
+    fn unconfigured_commands_use_defaults() {
+        assert_eq!(
+            parse_cooldown_rule("say=user:1", "remark"),
+            CooldownRule::default_for("remark")
+        );
+        assert_eq!(parse_cooldown_rule("", "joke list"), None);
+        assert_eq!(
+            parse_cooldown_rule("", "joke tell"),
+            CooldownRule::default_for("joke tell")
+        );
+    }
+
+    #[test]
+    fn rules_can_be_turned_off() {
+        assert_eq!(parse_cooldown_rule("say=off", "say"), None);
+        assert_eq!(parse_cooldown_rule("say=user:0", "say"), None);
+    }
+
+    #[test]
+    fn malformed_scopes_are_ignored() {
+        assert_eq!(
+            parse_cooldown_rule(" say = user:x, channel:4, galaxy:9, guild", "say"),
+            Some(CooldownRule {
+                user: None,
+                channel: Some(4),
+                guild: None,
+            })
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use crate::{
+    types::types::Error,
+    utils::{db::create_or_open_db, env, metrics::time_storage},
+};
+use chrono::NaiveDate;
+
+fn open() -> pickledb::PickleDb {
+    create_or_open_db(env::data_path() + "daily.db")
+}
+
+/// Local date a guild last got its joke of the day
+pub fn last_posted(guild_id: u64) -> Option<NaiveDate> {
+    open().get::<NaiveDate>(&format!("posted:{}", guild_id))
+}
+
+pub fn mark_posted(guild_id: u64, date: NaiveDate) -> Result<(), Error> {
+    let mut db = open();
+    time_storage("write", || db.set(&format!("posted:{}", guild_id), &date))?;
+    Ok(())
+}
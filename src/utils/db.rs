@@ -1,15 +1,22 @@
+use crate::utils::{env, metrics::time_storage};
 use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 
 pub fn create_or_open_db(path: String) -> PickleDb {
     //pickle db
     //2
     //3
     // third
-    let opendb = match PickleDb::load(
-        path.clone(),
-        PickleDbDumpPolicy::AutoDump,
-        SerializationMethod::Json,
-    ) {
+    let opendb = match time_storage("open", || {
+        PickleDb::load(
+            path.clone(),
+            PickleDbDumpPolicy::AutoDump,
+            SerializationMethod::Json,
+        )
+    }) {
         Ok(opendb) => opendb,
         Err(_e) => {
             println!("Creating new db at: {}", path);
@@ -22,3 +29,32 @@ pub fn create_or_open_db(path: String) -> PickleDb {
     };
     opendb
 }
+
+/// Held for the whole of a read-modify-write. Events are handled concurrently and every store reopens
+/// its file, so two unguarded updates can read the same state and one silently overwrites the other.
+static UPDATES: Mutex<()> = Mutex::new(());
+
+/// Run `update` while no other store update is running. Don't nest these.
+pub fn exclusive<T>(update: impl FnOnce() -> T) -> T {
+    let _guard = UPDATES.lock().unwrap_or_else(|e| e.into_inner());
+    update()
+}
+
+/// How long a storage probe result is reused, so frequent readiness probes don't each write a file
+const PROBE_CACHE: Duration = Duration::from_secs(5);
+
+static LAST_PROBE: Mutex<Option<(Instant, bool)>> = Mutex::new(None);
+
+/// Whether the data directory currently accepts writes. Blocks on file IO.
+pub fn storage_writable() -> bool {
+    if let Some((at, writable)) = *LAST_PROBE.lock().unwrap() {
+        if at.elapsed() < PROBE_CACHE {
+            return writable;
+        }
+    }
+    let mut db = create_or_open_db(env::data_path() + "health.db");
+    let writable =
+        time_storage("write", || db.set("probe", &chrono::Utc::now().timestamp())).is_ok();
+    *LAST_PROBE.lock().unwrap() = Some((Instant::now(), writable));
+    writable
+}
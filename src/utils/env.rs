@@ -1,9 +1,15 @@
+use std::{
+    collections::{HashMap, HashSet},
+    env::VarError,
+    sync::{LazyLock, RwLock},
+};
+
 pub fn discord_token() -> String {
-    let bot_name = std::env::var("BOT").expect("BOT environment variable not set");
+    let bot_name = var("BOT").expect("BOT environment variable not set");
     match bot_name.to_lowercase().as_str() {
-        "rustical" => std::env::var("RUSTICAL").unwrap_or_else(|_| "default_token".into()),
-        "moneymouth" => std::env::var("MONEYMOUTH").unwrap_or_else(|_| "default_token".into()),
-        "fretter" => std::env::var("FRETTER").unwrap_or_else(|_| "default_token".into()),
+        "rustical" => var("RUSTICAL").unwrap_or_else(|_| "default_token".into()),
+        "moneymouth" => var("MONEYMOUTH").unwrap_or_else(|_| "default_token".into()),
+        "fretter" => var("FRETTER").unwrap_or_else(|_| "default_token".into()),
         _ => {
             panic!("Unknown bot specified in .env")
         }
@@ -11,19 +17,125 @@ pub fn discord_token() -> String {
 }
 
 pub fn data_path() -> String {
-    std::env::var("DATA_PATH").unwrap_or_else(|_| "data/".into())
+    var("DATA_PATH").unwrap_or_else(|_| "data/".into())
 }
 
 pub fn static_path() -> String {
-    std::env::var("STATIC_PATH").unwrap_or_else(|_| "static/".into())
+    var("STATIC_PATH").unwrap_or_else(|_| "static/".into())
 }
 
 pub fn laptop() -> String {
-    std::env::var("LAPTOP").unwrap_or_else(|_| "0".into())
+    var("LAPTOP").unwrap_or_else(|_| "0".into())
+}
+
+pub fn http_host() -> String {
+    var("HTTP_HOST").unwrap_or_else(|_| "0.0.0.0".into())
+}
+
+pub fn http_port() -> u16 {
+    var("HTTP_PORT")
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(8080)
+}
+
+pub fn dashboard_token() -> String {
+    var("DASHBOARD_TOKEN").unwrap_or_default()
+}
+
+pub fn api_token() -> String {
+    var("API_TOKEN").unwrap_or_default()
+}
+
+pub fn webhooks_path() -> String {
+    var("WEBHOOKS_PATH").unwrap_or_else(|_| data_path() + "webhooks.json")
+}
+
+pub fn owners() -> String {
+    var("OWNERS").unwrap_or_default()
+}
+
+/// Values read by the last [`reload`], consulted before the process environment.
+/// Kept here rather than written back with `set_var`, which isn't safe while other threads read the environment.
+static RELOADED: LazyLock<RwLock<Option<HashMap<String, String>>>> =
+    LazyLock::new(|| RwLock::new(None));
+
+/// Variables that [`load`] copied into the environment from .env at startup
+static FROM_FILE: LazyLock<RwLock<HashSet<String>>> = LazyLock::new(|| RwLock::new(HashSet::new()));
+
+/// A variable as of the last reload, falling back to the environment the process started with.
+/// Once reloaded, a variable that came from .env and has since been removed from it is unset.
+fn var(key: &str) -> Result<String, VarError> {
+    if let Some(values) = RELOADED.read().unwrap().as_ref() {
+        if let Some(value) = values.get(key) {
+            return Ok(value.clone());
+        }
+        if FROM_FILE.read().unwrap().contains(key) {
+            return Err(VarError::NotPresent);
+        }
+    }
+    std::env::var(key)
+}
+
+/// Load .env into the environment at startup, remembering which variables came from it.
+/// Variables already set in the environment win, as with `dotenv::dotenv`.
+// dotenv_iter is deprecated but it's the only way to read values without setting them
+#[allow(deprecated)]
+pub fn load() {
+    let Ok(items) = dotenv::dotenv_iter() else {
+        return;
+    };
+    let keys = items
+        .filter_map(Result::ok)
+        .map(|(key, _)| key)
+        .filter(|key| std::env::var_os(key).is_none())
+        .collect();
+    *FROM_FILE.write().unwrap() = keys;
+    dotenv::dotenv().ok();
+}
+
+/// Variables only read at startup, so changing them in .env needs a restart
+pub const RESTART_ONLY: &[&str] = &[
+    "BOT",
+    "RUSTICAL",
+    "MONEYMOUTH",
+    "FRETTER",
+    "OWNERS",
+    "HTTP_HOST",
+    "HTTP_PORT",
+    "COMMAND_REGISTRATION",
+    "TEST_GUILDS",
+];
+
+/// Re-read .env, overriding variables already set; ones removed from it since startup become unset.
+/// Returns how many were loaded.
+// dotenv_iter is deprecated but it's the only way to read values without setting them
+#[allow(deprecated)]
+pub fn reload() -> Result<usize, dotenv::Error> {
+    let mut values = HashMap::new();
+    for item in dotenv::dotenv_iter()? {
+        let (key, value) = item?;
+        values.insert(key, value);
+    }
+    let count = values.len();
+    *RELOADED.write().unwrap() = Some(values);
+    Ok(count)
+}
+
+pub fn command_registration() -> String {
+    var("COMMAND_REGISTRATION").unwrap_or_else(|_| "global".into())
+}
+
+pub fn test_guilds() -> String {
+    var("TEST_GUILDS").unwrap_or_default()
+}
+
+pub fn error_channel() -> String {
+    var("ERROR_CHANNEL").unwrap_or_default()
 }
 
-pub fn clear_password() -> String {
-    std::env::var("CLEAR_PASSWORD").unwrap_or_else(|_| "default_clear_password".into())
+pub fn cooldowns() -> String {
+    var("COOLDOWNS").unwrap_or_default()
 }
 
 //new env function
@@ -0,0 +1,40 @@
+use crate::{
+    types::{guild_settings::GuildSettings, types::Error},
+    utils::{
+        db::{create_or_open_db, exclusive},
+        env,
+        metrics::time_storage,
+    },
+};
+
+pub fn load_guild_settings(guild_id: u64) -> GuildSettings {
+    let db = create_or_open_db(env::data_path() + "guilds.db");
+    db.get::<GuildSettings>(&guild_id.to_string())
+        .unwrap_or_default()
+}
+
+/// Change a guild's settings and save them, returning what `change` returned.
+/// Loading and saving happen under one lock so concurrent changes to different settings aren't lost.
+pub fn update_guild_settings<T>(
+    guild_id: u64,
+    change: impl FnOnce(&mut GuildSettings) -> T,
+) -> Result<T, Error> {
+    exclusive(|| {
+        let mut db = create_or_open_db(env::data_path() + "guilds.db");
+        let mut settings = db
+            .get::<GuildSettings>(&guild_id.to_string())
+            .unwrap_or_default();
+        let result = change(&mut settings);
+        time_storage("write", || db.set(&guild_id.to_string(), &settings))?;
+        Ok(result)
+    })
+}
+
+/// Every guild that has saved settings
+pub fn all_guild_settings() -> Vec<(u64, GuildSettings)> {
+    let db = create_or_open_db(env::data_path() + "guilds.db");
+    db.get_all()
+        .iter()
+        .filter_map(|key| Some((key.parse().ok()?, db.get::<GuildSettings>(key)?)))
+        .collect()
+}
@@ -0,0 +1,172 @@
+use crate::{
+    types::{
+        joke::{Joke, JokeCollection},
+        rating::JokeRating,
+    },
+    utils::{
+        guild_settings::load_guild_settings,
+        ratings::{get_rating, joke_key},
+    },
+};
+use poise::serenity_prelude::{
+    ButtonStyle, Colour, CreateActionRow, CreateAllowedMentions, CreateButton, CreateEmbed,
+    CreateEmbedFooter, CreateInteractionResponseMessage, CreateMessage,
+};
+
+/// Creeper green
+const EMBED_COLOUR: Colour = Colour::new(0x4caf50);
+
+/// A joke ready to post, as an embed or in the plain text format
+pub struct JokeMessage {
+    content: Option<String>,
+    embed: Option<CreateEmbed>,
+    components: Vec<CreateActionRow>,
+}
+
+impl JokeMessage {
+    pub fn into_reply(self) -> poise::CreateReply {
+        let mut reply = poise::CreateReply::default()
+            .components(self.components)
+            .allowed_mentions(CreateAllowedMentions::new());
+        if let Some(content) = self.content {
+            reply = reply.content(content);
+        }
+        if let Some(embed) = self.embed {
+            reply = reply.embed(embed);
+        }
+        reply
+    }
+
+    pub fn into_message(self) -> CreateMessage {
+        let mut message = CreateMessage::new()
+            .components(self.components)
+            .allowed_mentions(CreateAllowedMentions::new());
+        if let Some(content) = self.content {
+            message = message.content(content);
+        }
+        if let Some(embed) = self.embed {
+            message = message.embed(embed);
+        }
+        message
+    }
+
+    pub fn into_response(self) -> CreateInteractionResponseMessage {
+        let mut response = CreateInteractionResponseMessage::new()
+            .components(self.components)
+            .allowed_mentions(CreateAllowedMentions::new());
+        if let Some(content) = self.content {
+            response = response.content(content);
+        }
+        if let Some(embed) = self.embed {
+            response = response.embed(embed);
+        }
+        response
+    }
+}
+
+/// Whether a guild asked for jokes as plain text instead of embeds
+pub fn plain_jokes(guild_id: Option<u64>) -> bool {
+    guild_id.is_some_and(|id| load_guild_settings(id).plain_jokes)
+}
+
+/// Render a joke for posting. `remark` is shown with the joke; otherwise a button can reveal one.
+pub fn render_joke(
+    collection: &JokeCollection,
+    joke: &Joke,
+    remark: Option<&str>,
+    heading: Option<&str>,
+    plain: bool,
+) -> JokeMessage {
+    let rating = get_rating(&joke_key(&collection.key, joke.id));
+    let components = vec![joke_buttons(
+        &collection.key,
+        joke.id,
+        &rating,
+        remark.is_none() && !joke.remarks.is_empty(),
+    )];
+
+    if plain {
+        let mut content = joke_content(joke, remark);
+        if let Some(heading) = heading {
+            content = format!("**{}**\n{}", heading, content);
+        }
+        return JokeMessage {
+            content: Some(content),
+            embed: None,
+            components,
+        };
+    }
+
+    let mut description = joke.text.clone();
+    // Attribution goes here rather than the footer so community mentions render
+    if let Some(source) = joke.source.as_ref().filter(|s| **s != collection.title) {
+        description.push_str(&format!("\n-# — {}", source));
+    }
+    let title = match heading {
+        Some(heading) => format!("{} · #{}", heading, joke.id),
+        None => format!("#{}", joke.id),
+    };
+    let mut embed = CreateEmbed::new()
+        .title(title)
+        .description(description)
+        .colour(EMBED_COLOUR)
+        .footer(CreateEmbedFooter::new(joke_footer(collection, &rating)));
+    if let Some(remark) = remark {
+        embed = embed.field("Remark", format!("||{}||", remark), false);
+    }
+    JokeMessage {
+        content: None,
+        embed: Some(embed),
+        components,
+    }
+}
+
+/// The plain text format: number, joke, and the remark in a code block
+pub fn joke_content(joke: &Joke, remark: Option<&str>) -> String {
+    match remark {
+        Some(remark) => format!("-# #{}:\n{}\n```\n{}```", joke.id, joke.text, remark),
+        None => format!("-# #{}:\n{}", joke.id, joke.text),
+    }
+}
+
+/// Collection and rating, shown under an embedded joke
+pub fn joke_footer(collection: &JokeCollection, rating: &JokeRating) -> String {
+    let (up, down) = rating.counts(None);
+    format!(
+        "{} · 👍 {} 👎 {} · rating {:+}",
+        collection.title,
+        up,
+        down,
+        rating.score(None)
+    )
+}
+
+/// Vote and favourite buttons shown under a joke, plus one to reveal a remark when it has one
+pub fn joke_buttons(
+    collection: &str,
+    number: u32,
+    rating: &JokeRating,
+    remark_button: bool,
+) -> CreateActionRow {
+    let (up, down) = rating.counts(None);
+    let key = joke_key(collection, number);
+    let mut buttons = vec![
+        CreateButton::new(format!("joke:vote:{}:up", key))
+            .label(format!("👍 {}", up))
+            .style(ButtonStyle::Secondary),
+        CreateButton::new(format!("joke:vote:{}:down", key))
+            .label(format!("👎 {}", down))
+            .style(ButtonStyle::Secondary),
+        CreateButton::new(format!("joke:favourite:{}", key))
+            .label("⭐")
+            .style(ButtonStyle::Secondary),
+    ];
+    if remark_button {
+        buttons.push(
+            CreateButton::new(format!("joke:remark:{}", key))
+                .label("Show remark")
+                .style(ButtonStyle::Primary),
+        );
+    }
+    CreateActionRow::Buttons(buttons)
+}
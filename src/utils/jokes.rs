@@ -0,0 +1,601 @@
+use crate::{
+    types::{
+        joke::{Joke, JokeCollection, JokeLibrary, JokeStore},
+        submission::SubmissionStatus,
+        types::Error,
+    },
+    utils::{
+        env,
+        guild_settings::load_guild_settings,
+        log::log,
+        ratings::{all_ratings, parse_joke_key},
+        rotation::draw,
+        submissions::guild_submissions,
+    },
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use toml::Spanned;
+
+/// Layout of a joke file:
+///
+/// ```toml
+/// title = "Jokes for Minecrafters"    # display name, defaults to the file name
+/// source = "Jokes for Minecrafters"   # attribution for jokes without their own
+///
+/// [[joke]]
+/// id = 1
+/// text = """
+/// What do you call a creeper with a bomb in his hands?
+/// It doesn't matter; he will be blown to smithereens!"""
+/// remarks = ["An early example of the calibre of jokes we'll be receiving."]
+/// tags = ["creeper"]
+/// ```
+#[derive(serde::Serialize, serde::Deserialize)]
+struct JokeFile<J> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(rename = "joke")]
+    jokes: Vec<J>,
+}
+
+/// Load and validate every collection in `STATIC_PATH/jokes/`, one per `.toml` file
+pub fn load_jokes() -> Result<JokeLibrary, Error> {
+    let directory = env::static_path() + "jokes/";
+    let mut paths: Vec<std::path::PathBuf> = std::fs::read_dir(&directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    let mut collections = Vec::new();
+    for path in paths {
+        let file = path.to_string_lossy().into_owned();
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let (title, jokes) = parse_joke_file(&file, &std::fs::read_to_string(&path)?)?;
+        log(format!("Loaded {} jokes from {}", jokes.len(), name));
+        collections.push(JokeCollection::new(
+            name.clone(),
+            title.unwrap_or(name),
+            jokes,
+        ));
+    }
+    if collections.is_empty() {
+        return Err(Error::Content {
+            file: directory,
+            line: 1,
+            message: "no joke files found".to_string(),
+        });
+    }
+    Ok(JokeLibrary::new(collections))
+}
+
+/// Load the joke files again and swap them in if every one is valid.
+/// On any error the jokes already loaded stay in place. Returns the number of collections and jokes.
+pub fn reload_jokes(store: &JokeStore) -> Result<(usize, usize), Error> {
+    let library = load_jokes()?;
+    let collections = library.collections().count();
+    let jokes = library.collections().map(|c| c.len()).sum();
+    store.replace(library);
+    Ok((collections, jokes))
+}
+
+/// Name of the per-guild collection of approved submissions
+pub const COMMUNITY: &str = "community";
+
+/// A guild's approved submissions as a collection, if it has any
+pub fn community_collection(guild_id: u64) -> Option<Arc<JokeCollection>> {
+    let jokes: Vec<Joke> = guild_submissions(guild_id, SubmissionStatus::Approved)
+        .into_iter()
+        .filter_map(|submission| {
+            Some(Joke {
+                id: submission.joke_id?,
+                text: submission.text,
+                remarks: submission.remark.into_iter().collect(),
+                tags: Vec::new(),
+                source: Some(format!("<@{}>", submission.author_id)),
+            })
+        })
+        .collect();
+    if jokes.is_empty() {
+        return None;
+    }
+    Some(Arc::new(
+        JokeCollection::new(COMMUNITY.to_string(), "Community jokes".to_string(), jokes)
+            .with_key(format!("{}.{}", COMMUNITY, guild_id)),
+    ))
+}
+
+/// The guild a `community.<guild>` collection key belongs to.
+/// Community jokes are numbered per guild, so their keys carry the guild.
+pub fn community_guild(key: &str) -> Option<u64> {
+    key.strip_prefix(COMMUNITY)?.strip_prefix('.')?.parse().ok()
+}
+
+/// Collections usable in a guild, or all of them outside one
+pub fn enabled_collections(
+    library: &JokeLibrary,
+    guild_id: Option<u64>,
+) -> Vec<Arc<JokeCollection>> {
+    let disabled = guild_id
+        .map(|id| load_guild_settings(id).disabled_joke_collections)
+        .unwrap_or_default();
+    let mut collections: Vec<Arc<JokeCollection>> = library.collections().cloned().collect();
+    // A joke file called community.toml takes precedence over submissions
+    if library.get(COMMUNITY).is_none() {
+        collections.extend(guild_id.and_then(community_collection));
+    }
+    collections.retain(|collection| !disabled.contains(&collection.name));
+    collections
+}
+
+/// The collection a command asked for, else the guild's default, else the first enabled one
+pub fn resolve_collection(
+    library: &JokeLibrary,
+    guild_id: Option<u64>,
+    name: Option<&str>,
+) -> Result<Arc<JokeCollection>, Error> {
+    let enabled = enabled_collections(library, guild_id);
+    let name = name
+        .map(|name| name.trim().to_lowercase())
+        .or_else(|| guild_id.and_then(|id| load_guild_settings(id).default_joke_collection));
+    match name {
+        Some(name) => enabled
+            .into_iter()
+            .find(|collection| collection.name == name)
+            .ok_or_else(|| {
+                Error::NotFound(format!(
+                    "There is no joke collection called `{}` here.",
+                    name
+                ))
+            }),
+        None => enabled.into_iter().next().ok_or_else(|| {
+            Error::NotFound("Every joke collection is turned off in this server.".to_string())
+        }),
+    }
+}
+
+/// The next joke in a rotation through `collection`, so nothing repeats until everything has been shown.
+/// `rotation` names whose turn it is, e.g. `channel:<id>` for /joke in a channel.
+pub fn next_joke(
+    collection: &JokeCollection,
+    rotation: &str,
+    guild_id: Option<u64>,
+    with_remark: bool,
+) -> Result<Option<Joke>, Error> {
+    let weighted = guild_id.is_some_and(|id| load_guild_settings(id).weight_jokes_by_rating);
+    let scores: Option<HashMap<u32, i64>> = weighted.then(|| {
+        all_ratings()
+            .into_iter()
+            .filter_map(|(key, rating)| {
+                let (name, number) = parse_joke_key(&key)?;
+                (name == collection.key).then(|| (number, rating.score(None)))
+            })
+            .collect()
+    });
+    let bag = format!(
+        "{}:{}{}",
+        rotation,
+        collection.name,
+        if with_remark { ":remarks" } else { "" }
+    );
+    let id = draw(&bag, &collection.ids(with_remark), scores.as_ref())?;
+    Ok(id.and_then(|id| collection.get(id).cloned()))
+}
+
+/// Jokes matching a free text query against their text and tags, best match first.
+/// Tolerates small typos in longer words.
+pub fn search_jokes(
+    collections: &[Arc<JokeCollection>],
+    query: &str,
+    with_remark: bool,
+) -> Vec<(f64, Arc<JokeCollection>, Joke)> {
+    let terms: Vec<String> = words(query).filter(|term| term.len() > 1).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let mut results = Vec::new();
+    for collection in collections {
+        for id in collection.ids(with_remark) {
+            let Some(joke) = collection.get(id) else {
+                continue;
+            };
+            let text_words: Vec<String> = words(&joke.text).collect();
+            let text = joke.text.to_lowercase();
+            let mut matched = 0;
+            let mut score = 0.0;
+            for term in &terms {
+                let term_score = if joke.tags.iter().any(|tag| tag == term) {
+                    3.0
+                } else if text_words.contains(term) {
+                    2.0
+                } else if text.contains(term.as_str()) {
+                    1.5
+                } else if term.len() >= 4
+                    && text_words.iter().any(|word| within_one_edit(word, term))
+                {
+                    1.0
+                } else {
+                    0.0
+                };
+                if term_score > 0.0 {
+                    matched += 1;
+                    score += term_score;
+                }
+            }
+            // Most of the query has to match, not just one common word
+            if matched * 2 >= terms.len() && score > 0.0 {
+                results.push((score / terms.len() as f64, collection.clone(), joke.clone()));
+            }
+        }
+    }
+    results.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.2.id.cmp(&b.2.id)));
+    results
+}
+
+/// Lowercase words of `text`, keeping hyphenated words like knock-knock together
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '-')
+        .map(|word| word.trim_matches('-').to_lowercase())
+        .filter(|word| !word.is_empty())
+}
+
+/// Whether `a` can be turned into `b` by changing, adding or removing one character
+fn within_one_edit(a: &str, b: &str) -> bool {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (short, long) = if a.len() <= b.len() {
+        (&a, &b)
+    } else {
+        (&b, &a)
+    };
+    if long.len() - short.len() > 1 {
+        return false;
+    }
+    let prefix = short
+        .iter()
+        .zip(long.iter())
+        .take_while(|(x, y)| x == y)
+        .count();
+    if short.len() == long.len() {
+        prefix == short.len() || short[prefix + 1..] == long[prefix + 1..]
+    } else {
+        short[prefix..] == long[prefix + 1..]
+    }
+}
+
+/// Look up a joke by its `collection:number` key
+pub fn find_joke(library: &JokeLibrary, key: &str) -> Option<(Arc<JokeCollection>, Joke)> {
+    let (name, number) = parse_joke_key(key)?;
+    let collection = match library.get(name) {
+        Some(collection) => collection.clone(),
+        None => community_collection(community_guild(name)?)?,
+    };
+    let joke = collection.get(number)?.clone();
+    Some((collection, joke))
+}
+
+/// Parse and validate a joke file, reporting problems against the line they're on.
+/// Returns the file's title and its jokes in id order
+pub fn parse_joke_file(file: &str, content: &str) -> Result<(Option<String>, Vec<Joke>), Error> {
+    let error = |span: Option<std::ops::Range<usize>>, message: String| Error::Content {
+        file: file.to_string(),
+        line: span.map_or(1, |span| line_of(content, span.start)),
+        message,
+    };
+    let parsed: JokeFile<Spanned<Joke>> =
+        toml::from_str(content).map_err(|e| error(e.span(), e.message().to_string()))?;
+    if parsed.jokes.is_empty() {
+        return Err(error(None, "no jokes found".to_string()));
+    }
+
+    let mut seen = HashSet::new();
+    let mut jokes = Vec::with_capacity(parsed.jokes.len());
+    for spanned in parsed.jokes {
+        let span = spanned.span();
+        let mut joke = spanned.into_inner();
+        if joke.id == 0 {
+            return Err(error(Some(span), "joke ids start at 1".to_string()));
+        }
+        if !seen.insert(joke.id) {
+            return Err(error(
+                Some(span),
+                format!("joke #{} appears twice", joke.id),
+            ));
+        }
+        if joke.text.trim().is_empty() {
+            return Err(error(Some(span), format!("joke #{} has no text", joke.id)));
+        }
+        if joke.remarks.iter().any(|remark| remark.trim().is_empty()) {
+            return Err(error(
+                Some(span),
+                format!("joke #{} has an empty remark", joke.id),
+            ));
+        }
+        joke.text = joke.text.trim().to_string();
+        joke.tags = joke
+            .tags
+            .iter()
+            .map(|tag| tag.trim().to_lowercase())
+            .collect();
+        if joke.source.is_none() {
+            joke.source = parsed.source.clone();
+        }
+        jokes.push(joke);
+    }
+    jokes.sort_by_key(|joke| joke.id);
+    Ok((parsed.title, jokes))
+}
+
+/// 1-based line number of a byte offset
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+/// Convert the old line-based files (one joke per line with `\n` escapes, and alternating
+/// joke/remark lines) into a joke file. Run once with `rustical convert-jokes <jokes.md> <remarks.md> <out.toml>`.
+pub fn convert_markdown(
+    jokes_file: &str,
+    remarks_file: &str,
+    output: &str,
+) -> Result<usize, Error> {
+    let mut jokes: Vec<Joke> = lines(&std::fs::read_to_string(jokes_file)?)
+        .enumerate()
+        .map(|(position, (_, text))| Joke {
+            id: position as u32 + 1,
+            tags: guess_tags(&text),
+            text,
+            remarks: Vec::new(),
+            source: None,
+        })
+        .collect();
+
+    let remark_lines: Vec<(usize, String)> =
+        lines(&std::fs::read_to_string(remarks_file)?).collect();
+    if !remark_lines.len().is_multiple_of(2) {
+        let (line, _) = remark_lines.last().cloned().unwrap_or_default();
+        return Err(Error::Content {
+            file: remarks_file.to_string(),
+            line,
+            message: "joke has no remark after it".to_string(),
+        });
+    }
+    for pair in remark_lines.chunks(2) {
+        let target = normalise(&pair[0].1);
+        let found = jokes
+            .iter()
+            .position(|joke| normalise(&joke.text) == target)
+            // Some remarks cover two jokes told together; file them under the first
+            .or_else(|| {
+                jokes
+                    .iter()
+                    .position(|joke| target.contains(&normalise(&joke.text)))
+            });
+        match found {
+            Some(position) => jokes[position].remarks.push(pair[1].1.clone()),
+            None => {
+                return Err(Error::Content {
+                    file: remarks_file.to_string(),
+                    line: pair[0].0,
+                    message: "remark doesn't match any joke".to_string(),
+                })
+            }
+        }
+    }
+
+    let file: JokeFile<Joke> = JokeFile {
+        title: Some("Jokes for Minecrafters".to_string()),
+        source: Some("Jokes for Minecrafters".to_string()),
+        jokes,
+    };
+    let count = file.jokes.len();
+    let content = toml::to_string_pretty(&file).map_err(|e| Error::Internal(e.to_string()))?;
+    std::fs::write(output, content)?;
+    Ok(count)
+}
+
+/// Non-empty lines of `content` with their 1-based line numbers, `\n` escapes expanded
+fn lines(content: &str) -> impl Iterator<Item = (usize, String)> + '_ {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| (number + 1, line.trim_end().replace("\\n", "\n")))
+}
+
+/// Tags for the things jokes are usually about, so they can be searched
+fn guess_tags(text: &str) -> Vec<String> {
+    let text = text.to_lowercase();
+    let words: HashSet<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|word| word.strip_suffix('s').unwrap_or(word))
+        .collect();
+    let mut tags: Vec<String> = [
+        "creeper",
+        "zombie",
+        "skeleton",
+        "enderman",
+        "blaze",
+        "ghast",
+        "spider",
+        "villager",
+        "pig",
+        "cow",
+        "sheep",
+        "chicken",
+        "cat",
+        "wolf",
+        "tnt",
+        "diamond",
+        "noob",
+        "herobrine",
+        "steve",
+        "nether",
+        "redstone",
+        "pickaxe",
+        "sword",
+    ]
+    .iter()
+    .filter(|keyword| words.contains(*keyword))
+    .map(|keyword| keyword.to_string())
+    .collect();
+    if text.starts_with("knock, knock") {
+        tags.push("knock-knock".to_string());
+    }
+    if text.contains("what am i?") {
+        tags.push("riddle".to_string());
+    }
+    tags
+}
+
+/// Lowercased with whitespace collapsed, for comparing joke text
+fn normalise(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection() -> Arc<JokeCollection> {
+        let joke = |id, text: &str, tags: &[&str], remark: bool| Joke {
+            id,
+            text: text.to_string(),
+            remarks: if remark {
+                vec!["Heh.".to_string()]
+            } else {
+                Vec::new()
+            },
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            source: None,
+        };
+        Arc::new(JokeCollection::new(
+            "test".to_string(),
+            "Test jokes".to_string(),
+            vec![
+                joke(1, "Why did the creeper cross the road?", &["creeper"], true),
+                joke(
+                    2,
+                    "What do you call a skeleton in a cave?",
+                    &["skeleton"],
+                    false,
+                ),
+                joke(
+                    3,
+                    "Knock-knock. Who's there? Steve.",
+                    &["knock-knock"],
+                    false,
+                ),
+            ],
+        ))
+    }
+
+    fn ids(results: Vec<(f64, Arc<JokeCollection>, Joke)>) -> Vec<u32> {
+        results.into_iter().map(|(_, _, joke)| joke.id).collect()
+    }
+
+    #[test]
+    fn search_ranks_tags_and_words() {
+        let collections = [collection()];
+        assert_eq!(ids(search_jokes(&collections, "creeper", false)), vec![1]);
+        assert_eq!(
+            ids(search_jokes(&collections, "skeleton cave", false)),
+            vec![2]
+        );
+        assert_eq!(
+            ids(search_jokes(&collections, "knock-knock", false)),
+            vec![3]
+        );
+        assert!(search_jokes(&collections, "enderman", false).is_empty());
+        assert!(search_jokes(&collections, "a", false).is_empty());
+    }
+
+    #[test]
+    fn search_tolerates_typos() {
+        let collections = [collection()];
+        assert_eq!(ids(search_jokes(&collections, "skeletn", false)), vec![2]);
+        assert_eq!(
+            ids(search_jokes(&collections, "creepr road", false)),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn search_can_require_remarks() {
+        let collections = [collection()];
+        assert!(search_jokes(&collections, "skeleton", true).is_empty());
+        assert_eq!(ids(search_jokes(&collections, "creeper", true)), vec![1]);
+    }
+
+    #[test]
+    fn one_edit() {
+        assert!(within_one_edit("creeper", "creeper"));
+        assert!(within_one_edit("creeper", "creper"));
+        assert!(within_one_edit("creeper", "creepers"));
+        assert!(within_one_edit("creeper", "creeqer"));
+        assert!(!within_one_edit("creeper", "crepe"));
+        assert!(!within_one_edit("creeper", "cheeqer"));
+    }
+
+    #[test]
+    fn joke_files_parse_in_id_order() {
+        let content = r#"
+title = "Test jokes"
+source = "The internet"
+
+[[joke]]
+id = 2
+text = "  Second  "
+tags = ["Mobs"]
+
+[[joke]]
+id = 1
+text = "First"
+remarks = ["A remark"]
+source = "Someone"
+"#;
+        let (title, jokes) = parse_joke_file("test.toml", content).unwrap();
+        assert_eq!(title.as_deref(), Some("Test jokes"));
+        assert_eq!(jokes.iter().map(|j| j.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(jokes[1].text, "Second");
+        assert_eq!(jokes[1].tags, vec!["mobs"]);
+        assert_eq!(jokes[1].source.as_deref(), Some("The internet"));
+        assert_eq!(jokes[0].source.as_deref(), Some("Someone"));
+    }
+
+    fn error_line(content: &str) -> (usize, String) {
+        match parse_joke_file("test.toml", content) {
+            Err(Error::Content { line, message, .. }) => (line, message),
+            other => panic!("expected a content error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn joke_file_errors_point_at_the_line() {
+        let duplicate = "[[joke]]\nid = 1\ntext = \"a\"\n\n[[joke]]\nid = 1\ntext = \"b\"\n";
+        let (line, message) = error_line(duplicate);
+        assert_eq!(line, 5);
+        assert!(message.contains("twice"), "{}", message);
+
+        let empty = "[[joke]]\nid = 1\ntext = \"a\"\n\n[[joke]]\nid = 2\ntext = \" \"\n";
+        assert_eq!(error_line(empty).0, 5);
+
+        let syntax = "[[joke]]\nid = 1\ntext = \"a\n";
+        assert_eq!(error_line(syntax).0, 3);
+
+        let missing = "[[joke]]\nid = 1\n";
+        assert_eq!(error_line(missing).0, 1);
+
+        assert!(error_line("joke = []\n").1.contains("no jokes"));
+    }
+}
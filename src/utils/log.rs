@@ -3,11 +3,14 @@ use crate::{
         dblog::DBLog, 
         types::Error
     },
-    utils::db::create_or_open_db,
+    utils::{
+        bot::send_message_in_channel,
+        db::create_or_open_db,
+        metrics::time_storage,
+    },
 };
 use chrono::Utc;
-use poise::serenity_prelude::{self as serenity, Http};
-use serenity::model::id::ChannelId;
+use pickledb::PickleDb;
 use crate::utils::env;
 
 #[allow(dead_code)]
@@ -16,42 +19,46 @@ pub fn log(message: String) {
         "{}/logs.db",
         env::data_path()
     ));
-    if !db.lexists("logs") {
-        if db.lcreate("logs").is_err() {
-            return;
-        }
+    if !db.lexists("logs") && db.lcreate("logs").is_err() {
+        return;
     }
-    if db
-        .ladd(
-            "logs",
-            &DBLog {
-                timestamp: Utc::now(),
-                message: message.clone(),
-            },
-        )
-        .is_none()
-    {
+    let entry = DBLog {
+        timestamp: Utc::now(),
+        message: message.clone(),
+    };
+    if time_storage("write", || db.ladd("logs", &entry)).is_none() {
         return;
     }
-    if db.get::<bool>("realtime").unwrap_or(false) {
+    if realtime_enabled_in(&db) {
         send_realtime_log(&message);
     }
     println!("[Log]: {}", message);
 }
 
 fn send_realtime_log(message: &str) {
-    let channel = ChannelId::new(1160065321013620857);
-    let http = Http::new(&env::discord_token());
+    send_message_in_channel(&format!("[Log]: {}", message), 1160065321013620857);
+}
+
+fn realtime_enabled_in(db: &PickleDb) -> bool {
+    db.get::<bool>("realtime").unwrap_or(false)
+}
+
+pub fn realtime_enabled() -> bool {
+    let db = create_or_open_db(format!("{}/logs.db", env::data_path()));
+    realtime_enabled_in(&db)
+}
 
-    // Spawn a new Tokio task to send the message asynchronously
-    let message = message.to_string();
-    tokio::spawn(async move {
-        let _ = channel.say(&http, format!("[Log]: {}", message)).await;
-    });
+pub fn set_realtime(enabled: bool) -> Result<(), Error> {
+    let mut db = create_or_open_db(format!("{}/logs.db", env::data_path()));
+    time_storage("write", || db.set("realtime", &enabled))?;
+    Ok(())
 }
 
 pub fn load_all_logs() -> Result<Vec<DBLog>, Error> {
-    let db = create_or_open_db("data/logs.db".to_string());
+    let db = create_or_open_db(format!("{}/logs.db", env::data_path()));
+    if !db.lexists("logs") {
+        return Ok(Vec::new());
+    }
 
     let mut all_logs: Vec<DBLog> = Vec::new();
     for item_iter in db.liter("logs") {
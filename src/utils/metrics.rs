@@ -0,0 +1,83 @@
+use prometheus::{
+    register_histogram_vec, register_int_counter, register_int_counter_vec, Encoder,
+    HistogramVec, IntCounter, IntCounterVec, TextEncoder,
+};
+use std::{sync::LazyLock, time::Instant};
+
+pub static COMMANDS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "rustical_commands_total",
+        "Command invocations by command and outcome",
+        &["command", "outcome"]
+    )
+    .unwrap()
+});
+
+pub static COMMAND_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "rustical_command_duration_seconds",
+        "Time taken to run a command",
+        &["command"]
+    )
+    .unwrap()
+});
+
+pub static EVENT_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "rustical_event_handler_duration_seconds",
+        "Time spent in the gateway event handler",
+        &["event"]
+    )
+    .unwrap()
+});
+
+pub static STORAGE_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "rustical_storage_duration_seconds",
+        "Time spent reading and writing pickledb files",
+        &["operation"],
+        vec![0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]
+    )
+    .unwrap()
+});
+
+pub static OUTBOUND_FAILURES: LazyLock<IntCounter> = LazyLock::new(|| {
+    register_int_counter!(
+        "rustical_outbound_message_failures_total",
+        "Messages the bot failed to send"
+    )
+    .unwrap()
+});
+
+pub static GATEWAY_RECONNECTS: LazyLock<IntCounter> = LazyLock::new(|| {
+    register_int_counter!(
+        "rustical_gateway_reconnects_total",
+        "Gateway sessions resumed or re-identified after the first connect"
+    )
+    .unwrap()
+});
+
+/// Run a storage operation and record how long it took
+pub fn time_storage<T>(operation: &str, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = f();
+    STORAGE_DURATION
+        .with_label_values(&[operation])
+        .observe(started.elapsed().as_secs_f64());
+    result
+}
+
+/// Everything registered, in the prometheus text format
+pub fn gather() -> String {
+    // Touch every metric so they show up before their first observation
+    LazyLock::force(&COMMANDS);
+    LazyLock::force(&COMMAND_DURATION);
+    LazyLock::force(&EVENT_DURATION);
+    LazyLock::force(&STORAGE_DURATION);
+    LazyLock::force(&OUTBOUND_FAILURES);
+    LazyLock::force(&GATEWAY_RECONNECTS);
+
+    let mut buffer = Vec::new();
+    let _ = TextEncoder::new().encode(&prometheus::gather(), &mut buffer);
+    String::from_utf8(buffer).unwrap_or_default()
+}
@@ -1,5 +1,21 @@
 pub mod bot;
+pub mod cooldowns;
+pub mod daily_jokes;
 pub mod db;
 pub mod env;
+pub mod guild_settings;
+pub mod joke_messages;
+pub mod jokes;
 pub mod log;
+pub mod metrics;
+pub mod permissions;
+pub mod ratings;
+pub mod registration;
+pub mod relay;
+pub mod reminders;
+pub mod rotation;
+pub mod schedules;
+pub mod submissions;
+pub mod time;
 pub mod translations;
+pub mod webhooks;
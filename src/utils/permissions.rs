@@ -0,0 +1,127 @@
+use crate::{
+    types::{
+        permission::PermissionLevel,
+        types::{Context, Error},
+    },
+    utils::guild_settings::load_guild_settings,
+};
+use poise::{
+    serenity_prelude::{self as serenity, Permissions},
+    ChoiceParameter,
+};
+use std::collections::HashSet;
+
+/// Level of the invoking user in the current guild
+pub async fn user_level(ctx: Context<'_>) -> PermissionLevel {
+    if ctx.framework().options().owners.contains(&ctx.author().id) {
+        return PermissionLevel::Owner;
+    }
+    let Some(guild_id) = ctx.guild_id() else {
+        return PermissionLevel::Everyone;
+    };
+    let Some(member) = ctx.author_member().await else {
+        return PermissionLevel::Everyone;
+    };
+
+    // Interactions carry the member's resolved permissions; fall back to the cache otherwise
+    let permissions = member.permissions.or_else(|| {
+        let guild = ctx.cache().guild(guild_id)?;
+        let channel = guild.channels.get(&ctx.channel_id())?;
+        Some(guild.user_permissions_in(channel, &member))
+    });
+    member_level(guild_id.get(), &member, permissions)
+}
+
+/// Level of the author of a chat message, for commands typed as messages
+pub async fn message_author_level(
+    ctx: &serenity::Context,
+    owners: &HashSet<serenity::UserId>,
+    message: &serenity::Message,
+) -> PermissionLevel {
+    if owners.contains(&message.author.id) {
+        return PermissionLevel::Owner;
+    }
+    let Some(guild_id) = message.guild_id else {
+        return PermissionLevel::Everyone;
+    };
+    let Ok(member) = guild_id.member(ctx, message.author.id).await else {
+        return PermissionLevel::Everyone;
+    };
+    let permissions = ctx.cache.guild(guild_id).and_then(|guild| {
+        let channel = guild.channels.get(&message.channel_id)?;
+        Some(guild.user_permissions_in(channel, &member))
+    });
+    member_level(guild_id.get(), &member, permissions)
+}
+
+/// Level of a guild member from their resolved permissions and roles. Doesn't know about owners.
+pub fn member_level(
+    guild_id: u64,
+    member: &serenity::Member,
+    permissions: Option<Permissions>,
+) -> PermissionLevel {
+    if permissions
+        .is_some_and(|p| p.contains(Permissions::ADMINISTRATOR) || p.contains(Permissions::MANAGE_GUILD))
+    {
+        return PermissionLevel::Admin;
+    }
+
+    let settings = load_guild_settings(guild_id);
+    if member
+        .roles
+        .iter()
+        .any(|role| settings.admin_roles.contains(&role.get()))
+    {
+        return PermissionLevel::Admin;
+    }
+    PermissionLevel::Everyone
+}
+
+/// Level required for `command` in `guild_id`, honouring guild overrides
+pub fn required_level(command: &str, guild_id: Option<u64>) -> PermissionLevel {
+    guild_id
+        .and_then(|id| load_guild_settings(id).command_levels.get(command).copied())
+        .unwrap_or_else(|| PermissionLevel::default_for(command))
+}
+
+/// Fail with `Error::Permission` unless the user's level is high enough for the command
+pub async fn check_permission(ctx: Context<'_>) -> Result<(), Error> {
+    let qualified = &ctx.command().qualified_name;
+    let command = qualified.split(' ').next().unwrap_or_default();
+    let required = required_level(command, ctx.guild_id().map(|g| g.get()));
+    if required == PermissionLevel::Everyone {
+        return Ok(());
+    }
+    let level = user_level(ctx).await;
+    if level >= required {
+        Ok(())
+    } else {
+        Err(Error::Permission(format!(
+            "You need the {} permission level to use /{}",
+            required.name(),
+            qualified
+        )))
+    }
+}
+
+/// Poise check for subcommands that need more than their group, e.g. the settings under /joke
+pub async fn require_admin(ctx: Context<'_>) -> Result<bool, Error> {
+    if user_level(ctx).await >= PermissionLevel::Admin {
+        Ok(true)
+    } else {
+        Err(Error::Permission(format!(
+            "You need the {} permission level to use /{}",
+            PermissionLevel::Admin.name(),
+            ctx.command().qualified_name
+        )))
+    }
+}
+
+/// Bot owners from the comma separated `OWNERS` variable
+pub fn owners() -> HashSet<serenity::UserId> {
+    crate::utils::env::owners()
+        .split(',')
+        .filter_map(|id| id.trim().parse::<u64>().ok())
+        .map(serenity::UserId::new)
+        .collect()
+}
@@ -0,0 +1,151 @@
+use crate::{
+    types::{
+        joke::JokeLibrary,
+        rating::{JokeRating, Vote},
+        types::Error,
+    },
+    utils::{
+        db::{create_or_open_db, exclusive},
+        env,
+        jokes::COMMUNITY,
+        log::log,
+        metrics::time_storage,
+    },
+};
+use std::collections::HashMap;
+
+fn open() -> pickledb::PickleDb {
+    create_or_open_db(env::data_path() + "ratings.db")
+}
+
+/// How ratings and favourites refer to a joke: `collection:number`
+pub fn joke_key(collection: &str, number: u32) -> String {
+    format!("{}:{}", collection, number)
+}
+
+/// Split a joke key back into collection and number
+pub fn parse_joke_key(key: &str) -> Option<(&str, u32)> {
+    let (collection, number) = key.rsplit_once(':')?;
+    Some((collection, number.parse().ok()?))
+}
+
+pub fn get_rating(key: &str) -> JokeRating {
+    open()
+        .get::<JokeRating>(&format!("rating:{}", key))
+        .unwrap_or_default()
+}
+
+/// Record a vote. Voting the same way twice takes the vote back.
+pub fn vote(
+    key: &str,
+    user_id: u64,
+    guild_id: Option<u64>,
+    value: i8,
+) -> Result<JokeRating, Error> {
+    exclusive(|| {
+        let mut db = open();
+        let db_key = format!("rating:{}", key);
+        let mut rating = db.get::<JokeRating>(&db_key).unwrap_or_default();
+        match rating.votes.get(&user_id) {
+            Some(existing) if existing.value == value => {
+                rating.votes.remove(&user_id);
+            }
+            _ => {
+                rating.votes.insert(user_id, Vote { value, guild_id });
+            }
+        }
+        time_storage("write", || db.set(&db_key, &rating))?;
+        Ok(rating)
+    })
+}
+
+/// Every rated joke with its votes
+pub fn all_ratings() -> Vec<(String, JokeRating)> {
+    let db = open();
+    db.get_all()
+        .iter()
+        .filter_map(|key| {
+            let joke = key.strip_prefix("rating:")?;
+            Some((joke.to_string(), db.get::<JokeRating>(key)?))
+        })
+        .collect()
+}
+
+pub fn favourites(user_id: u64) -> Vec<String> {
+    open()
+        .get::<Vec<String>>(&format!("favourites:{}", user_id))
+        .unwrap_or_default()
+}
+
+/// Add or remove a favourite, returning whether it is now a favourite
+pub fn toggle_favourite(user_id: u64, key: &str) -> Result<bool, Error> {
+    exclusive(|| {
+        let mut db = open();
+        let db_key = format!("favourites:{}", user_id);
+        let mut favourites = db.get::<Vec<String>>(&db_key).unwrap_or_default();
+        let added = if let Some(position) = favourites.iter().position(|f| f == key) {
+            favourites.remove(position);
+            false
+        } else {
+            favourites.push(key.to_string());
+            true
+        };
+        time_storage("write", || db.set(&db_key, &favourites))?;
+        Ok(added)
+    })
+}
+
+/// Move ratings and favourites off the old `community:<number>` keys, which didn't say whose
+/// community collection they meant. Votes know their guild and move to that guild's key;
+/// favourites don't, so they are dropped. A `community.toml` joke file still owns those keys.
+pub fn migrate_community_keys(library: &JokeLibrary) -> Result<(), Error> {
+    if library.get(COMMUNITY).is_some() {
+        return Ok(());
+    }
+    let mut db = open();
+    let legacy = format!("{}:", COMMUNITY);
+    let mut moved = 0;
+    for db_key in db.get_all() {
+        if let Some(number) = db_key
+            .strip_prefix("rating:")
+            .and_then(|key| key.strip_prefix(&legacy))
+        {
+            let rating = db.get::<JokeRating>(&db_key).unwrap_or_default();
+            let mut by_guild: HashMap<u64, JokeRating> = HashMap::new();
+            for (user_id, vote) in rating.votes {
+                if let Some(guild_id) = vote.guild_id {
+                    by_guild
+                        .entry(guild_id)
+                        .or_default()
+                        .votes
+                        .insert(user_id, vote);
+                }
+            }
+            for (guild_id, rating) in by_guild {
+                let new_key = format!("rating:{}.{}:{}", COMMUNITY, guild_id, number);
+                let mut merged = db.get::<JokeRating>(&new_key).unwrap_or_default();
+                merged.votes.extend(rating.votes);
+                time_storage("write", || db.set(&new_key, &merged))?;
+            }
+            time_storage("write", || db.rem(&db_key))?;
+            moved += 1;
+        } else if db_key.starts_with("favourites:") {
+            let favourites = db.get::<Vec<String>>(&db_key).unwrap_or_default();
+            let kept: Vec<String> = favourites
+                .iter()
+                .filter(|key| !key.starts_with(&legacy))
+                .cloned()
+                .collect();
+            if kept.len() != favourites.len() {
+                time_storage("write", || db.set(&db_key, &kept))?;
+            }
+        }
+    }
+    if moved > 0 {
+        log(format!(
+            "Moved ratings of {} community jokes to per-server keys",
+            moved
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use crate::{
+    types::types::{Data, Error},
+    utils::{env, log::log},
+};
+use pickledb::PickleDb;
+use poise::serenity_prelude::{Command, GuildId, Http};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+
+/// Where slash commands get registered on startup
+pub enum RegistrationMode {
+    Global,
+    Guilds(Vec<GuildId>),
+}
+
+pub fn registration_mode() -> RegistrationMode {
+    match env::command_registration().to_lowercase().as_str() {
+        "guild" | "guilds" => RegistrationMode::Guilds(
+            env::test_guilds()
+                .split(',')
+                .filter_map(|id| id.trim().parse::<u64>().ok())
+                .map(GuildId::new)
+                .collect(),
+        ),
+        _ => RegistrationMode::Global,
+    }
+}
+
+/// Stable fingerprint of the command definitions Discord would receive
+pub fn commands_hash(commands: &[poise::Command<Data, Error>]) -> String {
+    let builders = poise::builtins::create_application_commands(commands);
+    let json = serde_json::to_vec(&builders).unwrap_or_default();
+    hex::encode(Sha256::digest(json))
+}
+
+/// Key under which the last registered hash is kept, per scope
+pub fn registration_key(guild_id: Option<GuildId>) -> String {
+    match guild_id {
+        Some(id) => format!("commands_hash:guild:{}", id),
+        None => "commands_hash:global".to_string(),
+    }
+}
+
+pub fn remember_registration(db: &mut PickleDb, guild_id: Option<GuildId>, hash: &str) {
+    let _ = db.set(&registration_key(guild_id), &hash.to_string());
+}
+
+pub fn forget_registration(db: &mut PickleDb, guild_id: Option<GuildId>) {
+    let _ = db.rem(&registration_key(guild_id));
+}
+
+/// Whether this build's commands are what Discord has: the stored hash must match, and Discord
+/// must still list the same command names, since they can be deleted or replaced elsewhere
+async fn already_registered(
+    http: &Http,
+    commands: &[poise::Command<Data, Error>],
+    db: &PickleDb,
+    guild_id: Option<GuildId>,
+    hash: &str,
+) -> bool {
+    if db.get::<String>(&registration_key(guild_id)).as_deref() != Some(hash) {
+        return false;
+    }
+    let registered = match guild_id {
+        Some(guild_id) => guild_id.get_commands(http).await,
+        None => Command::get_global_commands(http).await,
+    };
+    let Ok(registered) = registered else {
+        return false;
+    };
+    let local: BTreeSet<&str> = commands
+        .iter()
+        .filter(|c| c.slash_action.is_some() || !c.subcommands.is_empty())
+        .map(|c| c.name.as_str())
+        .collect();
+    let remote: BTreeSet<&str> = registered.iter().map(|c| c.name.as_str()).collect();
+    local == remote
+}
+
+/// Register commands for the configured mode, skipping scopes that are already up to date
+pub async fn register_on_startup(
+    http: &Http,
+    commands: &[poise::Command<Data, Error>],
+    db: &mut PickleDb,
+) -> Result<(), Error> {
+    let hash = commands_hash(commands);
+    match registration_mode() {
+        RegistrationMode::Global => {
+            if already_registered(http, commands, db, None, &hash).await {
+                println!("Global commands unchanged, skipping registration");
+                return Ok(());
+            }
+            if let Err(e) = poise::builtins::register_globally(http, commands).await {
+                forget_registration(db, None);
+                return Err(e.into());
+            }
+            remember_registration(db, None, &hash);
+            log("Registered commands globally".to_string());
+        }
+        RegistrationMode::Guilds(guild_ids) => {
+            if guild_ids.is_empty() {
+                println!("COMMAND_REGISTRATION is guild but TEST_GUILDS is empty");
+            }
+            for guild_id in guild_ids {
+                if already_registered(http, commands, db, Some(guild_id), &hash).await {
+                    println!(
+                        "Commands in guild {} unchanged, skipping registration",
+                        guild_id
+                    );
+                    continue;
+                }
+                if let Err(e) = poise::builtins::register_in_guild(http, commands, guild_id).await {
+                    forget_registration(db, Some(guild_id));
+                    return Err(e.into());
+                }
+                remember_registration(db, Some(guild_id), &hash);
+                log(format!("Registered commands in guild {}", guild_id));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use crate::{
+    types::types::Error,
+    utils::{bot::send, guild_settings::load_guild_settings, log::log},
+};
+use poise::serenity_prelude::{
+    self as serenity, ChannelId, CreateAllowedMentions, CreateMessage, GuildChannel, GuildId,
+    Permissions, User,
+};
+
+/// Make sure `user` may have the bot post into `channel_id`:
+/// relaying is enabled in their guild, the channel is in that guild, and they could post there themselves
+pub async fn check_relay_target(
+    ctx: &serenity::Context,
+    guild_id: Option<GuildId>,
+    user: &User,
+    channel_id: ChannelId,
+) -> Result<GuildChannel, Error> {
+    let guild_id = guild_id.ok_or_else(|| {
+        Error::Permission("The bot can only repeat messages inside a server.".to_string())
+    })?;
+    if load_guild_settings(guild_id.get()).relay_disabled {
+        return Err(Error::Permission(
+            "Making the bot say things is disabled in this server.".to_string(),
+        ));
+    }
+    check_post_target(ctx, guild_id, user, channel_id).await
+}
+
+/// Make sure `channel_id` is in `guild_id` and `user` could post there themselves.
+/// For bot posts an admin sets up, which don't depend on the relay switch.
+pub async fn check_post_target(
+    ctx: &serenity::Context,
+    guild_id: GuildId,
+    user: &User,
+    channel_id: ChannelId,
+) -> Result<GuildChannel, Error> {
+    let channel = channel_id
+        .to_channel(ctx)
+        .await
+        .ok()
+        .and_then(|c| c.guild())
+        .filter(|c| c.guild_id == guild_id)
+        .ok_or_else(|| Error::Permission("That channel isn't in this server.".to_string()))?;
+
+    let member = guild_id.member(ctx, user.id).await?;
+    let cached = ctx
+        .cache
+        .guild(guild_id)
+        .map(|guild| guild.user_permissions_in(&channel, &member));
+    let permissions = match cached {
+        Some(permissions) => permissions,
+        None => guild_id
+            .to_partial_guild(&ctx.http)
+            .await?
+            .user_permissions_in(&channel, &member),
+    };
+    if !permissions.contains(Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES) {
+        return Err(Error::Permission(format!(
+            "You can't post in <#{}> yourself, so I won't either.",
+            channel.id
+        )));
+    }
+    Ok(channel)
+}
+
+/// Mentions that relayed text is allowed to ping: nobody
+pub fn sanitised_mentions() -> CreateAllowedMentions {
+    CreateAllowedMentions::new()
+}
+
+/// Record who made the bot say what, and where
+pub fn audit(user: &User, channel_id: ChannelId, guild_id: Option<GuildId>, message: &str) {
+    log(format!(
+        "[Audit] {} ({}) made the bot say in <#{}> (guild {}): {}",
+        user.name,
+        user.id,
+        channel_id,
+        guild_id
+            .map(|g| g.to_string())
+            .unwrap_or_else(|| "none".into()),
+        message
+    ));
+}
+
+/// Post `message` on behalf of `user` after the relay checks have passed
+pub async fn relay_message(
+    ctx: &serenity::Context,
+    channel: &GuildChannel,
+    user: &User,
+    message: &str,
+) -> Result<serenity::Message, Error> {
+    relay(
+        ctx,
+        channel,
+        user,
+        CreateMessage::new().content(message),
+        message,
+    )
+    .await
+}
+
+/// Post a full message on behalf of `user`; `summary` is what goes in the audit log
+pub async fn relay(
+    ctx: &serenity::Context,
+    channel: &GuildChannel,
+    user: &User,
+    builder: CreateMessage,
+    summary: &str,
+) -> Result<serenity::Message, Error> {
+    let builder = builder.allowed_mentions(sanitised_mentions());
+    let sent = send(&ctx.http, channel.id.get(), builder).await?;
+    audit(user, channel.id, Some(channel.guild_id), summary);
+    Ok(sent)
+}
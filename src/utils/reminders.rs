@@ -0,0 +1,68 @@
+use crate::{
+    types::{reminder::Reminder, types::Error},
+    utils::{
+        db::{create_or_open_db, exclusive},
+        env,
+        metrics::time_storage,
+    },
+};
+
+fn open() -> pickledb::PickleDb {
+    create_or_open_db(env::data_path() + "reminders.db")
+}
+
+/// Store a new reminder, assigning it the next free id
+pub fn add_reminder(mut reminder: Reminder) -> Result<Reminder, Error> {
+    exclusive(|| {
+        let mut db = open();
+        let id = db.get::<u64>("next_id").unwrap_or(1);
+        db.set("next_id", &(id + 1))?;
+        reminder.id = id;
+        time_storage("write", || db.set(&format!("reminder:{}", id), &reminder))?;
+        Ok(reminder)
+    })
+}
+
+/// Change a stored reminder as it is now, returning false if it has been removed in the meantime
+pub fn update_reminder(id: u64, change: impl FnOnce(&mut Reminder)) -> Result<bool, Error> {
+    exclusive(|| {
+        let mut db = open();
+        let key = format!("reminder:{}", id);
+        let Some(mut reminder) = db.get::<Reminder>(&key) else {
+            return Ok(false);
+        };
+        change(&mut reminder);
+        time_storage("write", || db.set(&key, &reminder))?;
+        Ok(true)
+    })
+}
+
+pub fn remove_reminder(id: u64) -> Result<bool, Error> {
+    let mut db = open();
+    Ok(time_storage("write", || {
+        db.rem(&format!("reminder:{}", id))
+    })?)
+}
+
+pub fn get_reminder(id: u64) -> Option<Reminder> {
+    open().get::<Reminder>(&format!("reminder:{}", id))
+}
+
+pub fn load_reminders() -> Vec<Reminder> {
+    let db = open();
+    let mut reminders: Vec<Reminder> = db
+        .get_all()
+        .iter()
+        .filter(|key| key.starts_with("reminder:"))
+        .filter_map(|key| db.get::<Reminder>(key))
+        .collect();
+    reminders.sort_by_key(|r| r.remind_at);
+    reminders
+}
+
+pub fn user_reminders(user_id: u64) -> Vec<Reminder> {
+    load_reminders()
+        .into_iter()
+        .filter(|r| r.user_id == user_id)
+        .collect()
+}
@@ -0,0 +1,128 @@
+use crate::{
+    types::types::Error,
+    utils::{
+        db::{create_or_open_db, exclusive},
+        env,
+        metrics::time_storage,
+    },
+};
+use rand::{seq::SliceRandom, Rng};
+use std::collections::HashMap;
+
+fn open() -> pickledb::PickleDb {
+    create_or_open_db(env::data_path() + "rotation.db")
+}
+
+/// Draw the next joke from a shuffle bag, so every joke in `ids` comes up once before any repeats.
+/// With `scores`, better rated jokes tend to come up earlier in each round.
+pub fn draw(
+    bag: &str,
+    ids: &[u32],
+    scores: Option<&HashMap<u32, i64>>,
+) -> Result<Option<u32>, Error> {
+    if ids.is_empty() {
+        return Ok(None);
+    }
+    exclusive(|| {
+        let mut db = open();
+        let bag_key = format!("bag:{}", bag);
+        let last_key = format!("last:{}", bag);
+        let mut remaining = db.get::<Vec<u32>>(&bag_key).unwrap_or_default();
+        let next = take_next(&mut remaining, db.get::<u32>(&last_key), ids, scores);
+        time_storage("write", || db.set(&bag_key, &remaining))?;
+        if let Some(id) = next {
+            db.set(&last_key, &id)?;
+        }
+        Ok(next)
+    })
+}
+
+/// Take the next joke out of `remaining`, starting a new round when it runs out
+fn take_next(
+    remaining: &mut Vec<u32>,
+    last: Option<u32>,
+    ids: &[u32],
+    scores: Option<&HashMap<u32, i64>>,
+) -> Option<u32> {
+    // Jokes can disappear when content is reloaded
+    remaining.retain(|id| ids.contains(id));
+
+    if remaining.is_empty() {
+        *remaining = shuffled(ids, scores);
+        // Don't start a new round with the joke that ended the last one
+        if remaining.len() > 1 && remaining.last() == last.as_ref() {
+            let end = remaining.len() - 1;
+            remaining.swap(0, end);
+        }
+    }
+    remaining.pop()
+}
+
+/// A new round in draw order (last element first). Weighted by score when given.
+fn shuffled(ids: &[u32], scores: Option<&HashMap<u32, i64>>) -> Vec<u32> {
+    let mut rng = rand::rng();
+    let Some(scores) = scores else {
+        let mut ids = ids.to_vec();
+        ids.shuffle(&mut rng);
+        return ids;
+    };
+    // Weighted random order: each joke gets a key of u^(1/weight) and higher keys come first
+    let mut keyed: Vec<(f64, u32)> = ids
+        .iter()
+        .map(|id| {
+            let weight = (scores.get(id).copied().unwrap_or(0) + 2).max(1) as f64;
+            (rng.random::<f64>().powf(1.0 / weight), *id)
+        })
+        .collect();
+    keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+    keyed.into_iter().map(|(_, id)| id).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn every_joke_comes_up_once_per_round() {
+        let ids: Vec<u32> = (1..=10).collect();
+        let mut remaining = Vec::new();
+        let mut last = None;
+        for _ in 0..5 {
+            let round: HashSet<u32> = (0..ids.len())
+                .map(|_| {
+                    let id = take_next(&mut remaining, last, &ids, None).unwrap();
+                    last = Some(id);
+                    id
+                })
+                .collect();
+            assert_eq!(round.len(), ids.len());
+        }
+    }
+
+    #[test]
+    fn new_round_doesnt_repeat_the_last_joke() {
+        let ids = [1, 2];
+        for _ in 0..50 {
+            let mut remaining = Vec::new();
+            assert_ne!(take_next(&mut remaining, Some(2), &ids, None), Some(2));
+        }
+    }
+
+    #[test]
+    fn removed_jokes_leave_the_bag() {
+        let mut remaining = vec![5, 3, 1];
+        assert_eq!(take_next(&mut remaining, None, &[1, 5], None), Some(1));
+        assert_eq!(remaining, vec![5]);
+    }
+
+    #[test]
+    fn better_rated_jokes_tend_to_come_first() {
+        let ids = [1, 2];
+        let scores = HashMap::from([(1, 20), (2, -5)]);
+        let first_is_best = (0..500)
+            .filter(|_| shuffled(&ids, Some(&scores)).last() == Some(&1))
+            .count();
+        assert!(first_is_best > 400, "{} of 500", first_is_best);
+    }
+}
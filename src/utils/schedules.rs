@@ -0,0 +1,123 @@
+use crate::{
+    types::{
+        schedule::{ScheduledFile, ScheduledMessage},
+        types::Error,
+    },
+    utils::{
+        db::{create_or_open_db, exclusive},
+        env,
+        metrics::time_storage,
+    },
+};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use std::str::FromStr;
+
+fn open() -> pickledb::PickleDb {
+    create_or_open_db(env::data_path() + "schedules.db")
+}
+
+/// Store a new scheduled message, assigning it the next free id
+pub fn add_schedule(mut schedule: ScheduledMessage) -> Result<ScheduledMessage, Error> {
+    exclusive(|| {
+        let mut db = open();
+        let id = db.get::<u64>("next_id").unwrap_or(1);
+        db.set("next_id", &(id + 1))?;
+        schedule.id = id;
+        time_storage("write", || db.set(&format!("job:{}", id), &schedule))?;
+        Ok(schedule)
+    })
+}
+
+/// Change a stored message as it is now, returning false if it has been removed in the meantime
+pub fn update_schedule(id: u64, change: impl FnOnce(&mut ScheduledMessage)) -> Result<bool, Error> {
+    exclusive(|| {
+        let mut db = open();
+        let key = format!("job:{}", id);
+        let Some(mut schedule) = db.get::<ScheduledMessage>(&key) else {
+            return Ok(false);
+        };
+        change(&mut schedule);
+        time_storage("write", || db.set(&key, &schedule))?;
+        Ok(true)
+    })
+}
+
+/// Remove a scheduled message and any files kept for it
+pub fn remove_schedule(id: u64) -> Result<bool, Error> {
+    let mut db = open();
+    let key = format!("job:{}", id);
+    if let Some(schedule) = db.get::<ScheduledMessage>(&key) {
+        for file in &schedule.files {
+            let _ = std::fs::remove_file(files_path() + &file.path);
+        }
+    }
+    Ok(time_storage("write", || db.rem(&key))?)
+}
+
+fn files_path() -> String {
+    env::data_path() + "scheduled/"
+}
+
+/// Keep a file on disk until a scheduled message posts it. `name` has to be unique and is
+/// used instead of `filename` on disk, as uploaded file names can't be trusted in a path.
+pub fn save_schedule_file(
+    name: &str,
+    filename: &str,
+    bytes: &[u8],
+) -> Result<ScheduledFile, Error> {
+    std::fs::create_dir_all(files_path())?;
+    std::fs::write(files_path() + name, bytes)?;
+    Ok(ScheduledFile {
+        path: name.to_string(),
+        filename: filename.to_string(),
+    })
+}
+
+pub fn read_schedule_file(file: &ScheduledFile) -> Result<Vec<u8>, Error> {
+    Ok(std::fs::read(files_path() + &file.path)?)
+}
+
+pub fn load_schedules() -> Vec<ScheduledMessage> {
+    let db = open();
+    let mut schedules: Vec<ScheduledMessage> = db
+        .get_all()
+        .iter()
+        .filter(|key| key.starts_with("job:"))
+        .filter_map(|key| db.get::<ScheduledMessage>(key))
+        .collect();
+    schedules.sort_by_key(|s| s.next_run);
+    schedules
+}
+
+pub fn guild_schedules(guild_id: u64) -> Vec<ScheduledMessage> {
+    load_schedules()
+        .into_iter()
+        .filter(|s| s.guild_id == guild_id)
+        .collect()
+}
+
+/// Accepts standard 5-field cron (`min hour day month weekday`) as well as the 6/7-field form with seconds
+pub fn parse_cron(expression: &str) -> Result<cron::Schedule, Error> {
+    let fields = expression.split_whitespace().count();
+    let expression = if fields == 5 {
+        format!("0 {}", expression.trim())
+    } else {
+        expression.trim().to_string()
+    };
+    cron::Schedule::from_str(&expression)
+        .map_err(|e| Error::Validation(format!("Invalid cron expression: {}", e)))
+}
+
+/// Next time after `after` that `expression` fires, in the given timezone
+pub fn next_cron_run(
+    expression: &str,
+    timezone: Tz,
+    after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let schedule = parse_cron(expression).ok()?;
+    schedule
+        .after(&after.with_timezone(&timezone))
+        .next()
+        .map(|t| t.with_timezone(&Utc))
+}
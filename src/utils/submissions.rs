@@ -0,0 +1,72 @@
+use crate::{
+    types::{
+        submission::{JokeSubmission, SubmissionStatus},
+        types::Error,
+    },
+    utils::{
+        db::{create_or_open_db, exclusive},
+        env,
+        metrics::time_storage,
+    },
+};
+
+fn open() -> pickledb::PickleDb {
+    create_or_open_db(env::data_path() + "submissions.db")
+}
+
+/// Store a new submission, assigning it the next free id
+pub fn add_submission(mut submission: JokeSubmission) -> Result<JokeSubmission, Error> {
+    exclusive(|| {
+        let mut db = open();
+        let id = db.get::<u64>("next_id").unwrap_or(1);
+        db.set("next_id", &(id + 1))?;
+        submission.id = id;
+        time_storage("write", || {
+            db.set(&format!("submission:{}", id), &submission)
+        })?;
+        Ok(submission)
+    })
+}
+
+pub fn get_submission(id: u64) -> Option<JokeSubmission> {
+    open().get::<JokeSubmission>(&format!("submission:{}", id))
+}
+
+/// Record a moderator's decision. Approved jokes get the next number in the guild's community collection.
+/// Fails if someone else reviewed the submission first.
+pub fn review_submission(id: u64, approved: bool, reviewer: u64) -> Result<JokeSubmission, Error> {
+    exclusive(|| {
+        let mut db = open();
+        let mut submission = db
+            .get::<JokeSubmission>(&format!("submission:{}", id))
+            .filter(|s| s.status == SubmissionStatus::Pending)
+            .ok_or_else(|| Error::NotFound("Someone already reviewed that joke.".to_string()))?;
+        submission.reviewed_by = Some(reviewer);
+        if approved {
+            let key = format!("next_joke:{}", submission.guild_id);
+            let number = db.get::<u32>(&key).unwrap_or(1);
+            db.set(&key, &(number + 1))?;
+            submission.status = SubmissionStatus::Approved;
+            submission.joke_id = Some(number);
+        } else {
+            submission.status = SubmissionStatus::Rejected;
+        }
+        time_storage("write", || {
+            db.set(&format!("submission:{}", submission.id), &submission)
+        })?;
+        Ok(submission)
+    })
+}
+
+pub fn guild_submissions(guild_id: u64, status: SubmissionStatus) -> Vec<JokeSubmission> {
+    let db = open();
+    let mut submissions: Vec<JokeSubmission> = db
+        .get_all()
+        .iter()
+        .filter(|key| key.starts_with("submission:"))
+        .filter_map(|key| db.get::<JokeSubmission>(key))
+        .filter(|s| s.guild_id == guild_id && s.status == status)
+        .collect();
+    submissions.sort_by_key(|s| s.id);
+    submissions
+}
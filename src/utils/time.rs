@@ -0,0 +1,343 @@
+use crate::{types::types::Error, utils::guild_settings::load_guild_settings};
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use regex::Regex;
+
+/// Longest duration or furthest time ahead anything may be scheduled
+pub fn max_duration() -> Duration {
+    Duration::days(365)
+}
+
+/// Parse a duration like `90s`, `10m`, `1h30m`, `2d 4h` or `2 hours and 15 minutes`.
+/// A bare number means minutes. Durations over [`max_duration`] are rejected.
+pub fn parse_duration(input: &str) -> Result<Duration, Error> {
+    read_duration(input).unwrap_or_else(|| {
+        Err(Error::Validation(format!(
+            "I couldn't understand the duration `{}`.",
+            input.trim()
+        )))
+    })
+}
+
+/// `None` if `input` isn't a duration at all, an error if it is one but too long
+fn read_duration(input: &str) -> Option<Result<Duration, Error>> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return None;
+    }
+    let too_long = || {
+        Err(Error::Validation(format!(
+            "That's too long. The most I can wait is {} days.",
+            max_duration().num_days()
+        )))
+    };
+    if let Ok(minutes) = input.parse::<i64>() {
+        return match Duration::try_minutes(minutes) {
+            Some(d) if d > max_duration() => Some(too_long()),
+            Some(d) if d > Duration::zero() => Some(Ok(d)),
+            Some(_) => None,
+            None => Some(too_long()),
+        };
+    }
+
+    let part = Regex::new(r"(\d+)\s*([a-z]+)").unwrap();
+    let mut total = Duration::zero();
+    let mut rest = input.as_str();
+    while !rest.is_empty() {
+        let captures = part.captures(rest)?;
+        let whole = captures.get(0)?;
+        // Only separators may sit between the parts
+        let gap = rest[..whole.start()].trim();
+        if !(gap.is_empty() || gap == "," || gap == "and") {
+            return None;
+        }
+        let value: i64 = match captures[1].parse() {
+            Ok(value) => value,
+            // Too many digits for an i64
+            Err(_) => return Some(too_long()),
+        };
+        let part = match &captures[2] {
+            "s" | "sec" | "secs" | "second" | "seconds" => Duration::try_seconds(value),
+            "m" | "min" | "mins" | "minute" | "minutes" => Duration::try_minutes(value),
+            "h" | "hr" | "hrs" | "hour" | "hours" => Duration::try_hours(value),
+            "d" | "day" | "days" => Duration::try_days(value),
+            "w" | "week" | "weeks" => Duration::try_weeks(value),
+            _ => return None,
+        };
+        total = match part.and_then(|part| total.checked_add(&part)) {
+            Some(total) if total <= max_duration() => total,
+            _ => return Some(too_long()),
+        };
+        rest = rest[whole.end()..].trim_start_matches([' ', ',']);
+        rest = rest.strip_prefix("and").unwrap_or(rest).trim_start();
+    }
+    (total > Duration::zero()).then_some(Ok(total))
+}
+
+/// Parse a point in time, reading clock times in `timezone`. Understands:
+/// - relative times: `in 2h`, `90 minutes`
+/// - clock times: `18:30`, `9am`, `noon` (the next time the clock reads that)
+/// - days with optional times: `tomorrow 9am`, `friday at 17:00`, `next monday`
+/// - absolute times: `2025-12-24 09:00`
+///
+/// Times more than [`max_duration`] ahead are rejected.
+pub fn parse_time(input: &str, now: DateTime<Utc>, timezone: Tz) -> Result<DateTime<Utc>, Error> {
+    let time = read_time(input, now, timezone).unwrap_or_else(|| {
+        Err(Error::Validation(format!(
+            "I couldn't understand the time `{}`.",
+            input.trim()
+        )))
+    })?;
+    if time - now > max_duration() {
+        return Err(Error::Validation(format!(
+            "That's too far ahead. I can only look {} days ahead.",
+            max_duration().num_days()
+        )));
+    }
+    Ok(time)
+}
+
+/// `None` if `input` isn't a time at all, an error if it's a duration that is too long
+fn read_time(
+    input: &str,
+    now: DateTime<Utc>,
+    timezone: Tz,
+) -> Option<Result<DateTime<Utc>, Error>> {
+    let input = input.trim().to_lowercase();
+    let input = input.strip_prefix("at ").unwrap_or(&input);
+
+    if let Some(duration) = read_duration(input.strip_prefix("in ").unwrap_or(input)) {
+        return Some(duration.and_then(|duration| {
+            now.checked_add_signed(duration)
+                .ok_or_else(|| Error::Validation("That's too far ahead.".to_string()))
+        }));
+    }
+    for format in ["%Y-%m-%d %H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M"] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(input, format) {
+            return local_to_utc(datetime, timezone).map(Ok);
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return local_to_utc(date.and_time(default_time()), timezone).map(Ok);
+    }
+    if let Some(time) = parse_time_of_day(input) {
+        return next_local_time(time, now, timezone).map(Ok);
+    }
+
+    let today = now.with_timezone(&timezone).date_naive();
+    let (date, rest, weekday) = parse_day(input, today)?;
+    let rest = rest.trim();
+    let rest = rest.strip_prefix("at ").unwrap_or(rest);
+    let time = if rest.is_empty() {
+        default_time()
+    } else {
+        parse_time_of_day(rest)?
+    };
+    let candidate = local_to_utc(date.and_time(time), timezone)?;
+    // "friday 9am" on a friday afternoon means next week. A past "today 9am" stays in the past
+    // so the caller can reject it.
+    if candidate <= now && weekday && date == today {
+        return local_to_utc((date + Duration::days(7)).and_time(time), timezone).map(Ok);
+    }
+    Some(Ok(candidate))
+}
+
+/// Time used when only a day is given
+fn default_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(9, 0, 0).unwrap()
+}
+
+/// `17:30`, `9am`, `9:15 pm`, `noon`, `midnight`
+pub fn parse_time_of_day(input: &str) -> Option<NaiveTime> {
+    let input = input.trim().replace(' ', "");
+    match input.as_str() {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return NaiveTime::from_hms_opt(0, 0, 0),
+        _ => {}
+    }
+    if let Ok(time) = NaiveTime::parse_from_str(&input, "%H:%M") {
+        return Some(time);
+    }
+    let (clock, pm) = if let Some(clock) = input.strip_suffix("pm") {
+        (clock, true)
+    } else {
+        (input.strip_suffix("am")?, false)
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        None => (clock.parse::<u32>().ok()?, 0),
+    };
+    if hour == 0 || hour > 12 {
+        return None;
+    }
+    let hour = match (hour, pm) {
+        (12, false) => 0,
+        (12, true) => 12,
+        (h, true) => h + 12,
+        (h, false) => h,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Split a leading day word off `input`: `today`, `tomorrow`, `monday`, `next monday`.
+/// The flag is set when the day was given as a weekday.
+fn parse_day(input: &str, today: NaiveDate) -> Option<(NaiveDate, &str, bool)> {
+    let (word, rest) = input.split_once(' ').unwrap_or((input, ""));
+    match word {
+        "today" => return Some((today, rest, false)),
+        "tomorrow" => return Some((today + Duration::days(1), rest, false)),
+        _ => {}
+    }
+    let (word, rest, next) = if word == "next" {
+        let (word, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+        (word, rest, true)
+    } else {
+        (word, rest, false)
+    };
+    let weekday: Weekday = word.parse().ok()?;
+    let mut days_ahead = (weekday.num_days_from_monday() as i64
+        - today.weekday().num_days_from_monday() as i64)
+        .rem_euclid(7);
+    if next && days_ahead == 0 {
+        days_ahead = 7;
+    }
+    Some((today + Duration::days(days_ahead), rest, true))
+}
+
+/// The next time the clock in `timezone` reads `time`
+pub fn next_local_time(time: NaiveTime, now: DateTime<Utc>, timezone: Tz) -> Option<DateTime<Utc>> {
+    let today = now.with_timezone(&timezone).date_naive();
+    let candidate = local_to_utc(today.and_time(time), timezone)?;
+    if candidate > now {
+        Some(candidate)
+    } else {
+        local_to_utc((today + Duration::days(1)).and_time(time), timezone)
+    }
+}
+
+pub fn local_to_utc(datetime: NaiveDateTime, timezone: Tz) -> Option<DateTime<Utc>> {
+    timezone
+        .from_local_datetime(&datetime)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Timezone configured for a guild, UTC if none
+pub fn guild_timezone(guild_id: u64) -> Tz {
+    load_guild_settings(guild_id)
+        .timezone
+        .and_then(|tz| tz.parse().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Friday 13 June 2025, 15:00 UTC
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 13, 15, 0, 0).unwrap()
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("90s").unwrap(), Duration::seconds(90));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("2d 4h").unwrap(), Duration::hours(52));
+        assert_eq!(
+            parse_duration("2 hours and 15 minutes").unwrap(),
+            Duration::minutes(135)
+        );
+        assert_eq!(parse_duration("45").unwrap(), Duration::minutes(45));
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("0").is_err());
+        assert!(parse_duration("10 parsecs").is_err());
+    }
+
+    #[test]
+    fn huge_durations_are_rejected_not_overflowed() {
+        assert!(parse_duration("99999999999999w").is_err());
+        assert!(parse_duration("999999999999999999999999s").is_err());
+        assert!(parse_duration("9223372036854775807").is_err());
+        assert!(parse_duration("53w").is_err());
+        assert!(parse_duration("52w").is_ok());
+        assert!(parse_time("in 99999999999999w", now(), Tz::UTC).is_err());
+        assert!(parse_time("2099-01-01 09:00", now(), Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn relative_and_absolute_times() {
+        assert_eq!(parse_time("in 2h", now(), Tz::UTC).unwrap(), at(13, 17, 0));
+        assert_eq!(
+            parse_time("2025-06-20 09:30", now(), Tz::UTC).unwrap(),
+            at(20, 9, 30)
+        );
+        assert_eq!(
+            parse_time("tomorrow 9am", now(), Tz::UTC).unwrap(),
+            at(14, 9, 0)
+        );
+        assert_eq!(
+            parse_time("monday at 17:00", now(), Tz::UTC).unwrap(),
+            at(16, 17, 0)
+        );
+        assert!(parse_time("whenever", now(), Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn clock_times_in_the_guild_timezone() {
+        let berlin: Tz = "Europe/Berlin".parse().unwrap();
+        // 18:30 in Berlin is 16:30 UTC in summer
+        assert_eq!(parse_time("18:30", now(), berlin).unwrap(), at(13, 16, 30));
+    }
+
+    #[test]
+    fn bare_time_that_has_passed_is_tomorrow() {
+        assert_eq!(parse_time("9am", now(), Tz::UTC).unwrap(), at(14, 9, 0));
+        assert_eq!(parse_time("at 9am", now(), Tz::UTC).unwrap(), at(14, 9, 0));
+        assert_eq!(parse_time("6pm", now(), Tz::UTC).unwrap(), at(13, 18, 0));
+    }
+
+    #[test]
+    fn today_that_has_passed_stays_in_the_past() {
+        let time = parse_time("today 9am", now(), Tz::UTC).unwrap();
+        assert_eq!(time, at(13, 9, 0));
+        assert!(time <= now());
+        assert_eq!(
+            parse_time("today 6pm", now(), Tz::UTC).unwrap(),
+            at(13, 18, 0)
+        );
+    }
+
+    #[test]
+    fn weekday_that_has_passed_is_next_week() {
+        assert_eq!(
+            parse_time("friday 9am", now(), Tz::UTC).unwrap(),
+            at(20, 9, 0)
+        );
+        assert_eq!(
+            parse_time("friday 6pm", now(), Tz::UTC).unwrap(),
+            at(13, 18, 0)
+        );
+        assert_eq!(
+            parse_time("next friday 6pm", now(), Tz::UTC).unwrap(),
+            at(20, 18, 0)
+        );
+    }
+
+    #[test]
+    fn times_of_day() {
+        let hm = |h, m| NaiveTime::from_hms_opt(h, m, 0);
+        assert_eq!(parse_time_of_day("12am"), hm(0, 0));
+        assert_eq!(parse_time_of_day("12pm"), hm(12, 0));
+        assert_eq!(parse_time_of_day("9:15 pm"), hm(21, 15));
+        assert_eq!(parse_time_of_day("noon"), hm(12, 0));
+        assert_eq!(parse_time_of_day("13pm"), None);
+        assert_eq!(parse_time_of_day("25:00"), None);
+    }
+}
@@ -5,15 +5,17 @@ use crate::{
     },
     utils::{
         db::create_or_open_db,
-        env
+        env,
+        metrics::time_storage,
     }
 };
-use anyhow::Result;
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 struct DBTranslation {
     a: String,
     d: String,
+    #[serde(default)]
+    g: Option<u64>,
 }
 
 pub fn save_translation(translation: &Translation) -> Result<(), Error> {
@@ -22,14 +24,13 @@ pub fn save_translation(translation: &Translation) -> Result<(), Error> {
     if !db.lexists("translations") {
         db.lcreate("translations")?;
     }
-    db.ladd(
-        "translations",
-        &DBTranslation {
-            a: translation.abbreviation.clone(),
-            d: translation.definition.clone(),
-        },
-    )
-    .ok_or_else(|| Error::from("Failed to add translation to database"))?;
+    let entry = DBTranslation {
+        a: translation.abbreviation.clone(),
+        d: translation.definition.clone(),
+        g: translation.guild_id,
+    };
+    time_storage("write", || db.ladd("translations", &entry))
+        .ok_or_else(|| Error::Storage("Failed to add translation to database".to_string()))?;
     Ok(())
 }
 
@@ -47,6 +48,7 @@ pub fn load_translations() -> Result<Vec<Translation>, Error> {
         all_translations.push(Translation {
             abbreviation: db_translation.a,
             definition: db_translation.d,
+            guild_id: db_translation.g,
         });
     }
     Ok(all_translations)
@@ -61,3 +63,30 @@ pub fn get_translation(abbreviation: &String) -> Result<Option<Translation>, Err
     }
     Ok(None)
 }
+
+/// Remove a translation by abbreviation, returning whether one was found
+pub fn remove_translation(abbreviation: &str) -> Result<bool, Error> {
+    let db_path = env::data_path() + "translations.db";
+    let mut db = create_or_open_db(db_path);
+
+    if !db.lexists("translations") {
+        return Ok(false);
+    }
+
+    let position = db
+        .liter("translations")
+        .position(|item| {
+            item.get_item::<DBTranslation>()
+                .is_some_and(|t| t.a == abbreviation)
+        });
+    match position {
+        Some(pos) => {
+            time_storage("write", || db.lpop::<DBTranslation>("translations", pos))
+                .ok_or_else(|| {
+                    Error::Storage("Failed to remove translation from database".to_string())
+                })?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{
+    types::{
+        types::Error,
+        webhook::{WebhookConfig, WebhookTemplate},
+    },
+    utils::env,
+};
+use hmac::{Hmac, Mac};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use regex::Regex;
+use serde_json::Value;
+use sha2::Sha256;
+
+pub fn load_webhook_config() -> Result<WebhookConfig, Error> {
+    let path = env::webhooks_path();
+    let config: WebhookConfig = match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => WebhookConfig::default(),
+        Err(e) => return Err(Error::Io(e)),
+    };
+    if let Some((name, _)) = config
+        .routes
+        .iter()
+        .find(|(_, r)| r.secret.trim().is_empty())
+    {
+        return Err(Error::Validation(format!(
+            "webhook route {} has no secret",
+            name
+        )));
+    }
+    Ok(config)
+}
+
+/// Check a `sha256=<hex>` (or bare hex) HMAC signature of `body`
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let signature = signature.trim();
+    let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+    let Ok(expected) = hex::decode(signature) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// Replace `{{path.to.field}}` placeholders with values from the payload
+pub fn fill_template(template: &str, payload: &Value) -> String {
+    let placeholder = Regex::new(r"\{\{\s*([^}\s]+)\s*\}\}").unwrap();
+    placeholder
+        .replace_all(template, |captures: &regex::Captures| {
+            let mut value = payload;
+            for key in captures[1].split('.') {
+                let next = match value {
+                    Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+                    _ => value.get(key),
+                };
+                match next {
+                    Some(v) => value = v,
+                    None => return String::new(),
+                }
+            }
+            match value {
+                Value::String(s) => s.clone(),
+                Value::Null => String::new(),
+                other => other.to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Build the message content and embed for a payload
+pub fn render_webhook(
+    template: &WebhookTemplate,
+    payload: &Value,
+) -> (Option<String>, Option<CreateEmbed>) {
+    let fill = |t: &Option<String>, limit: usize| {
+        t.as_ref()
+            .map(|t| truncate(&fill_template(t, payload), limit))
+            .filter(|t| !t.trim().is_empty())
+    };
+
+    let content = fill(&template.content, 2000);
+    let title = fill(&template.title, 256);
+    let description = fill(&template.description, 4096);
+
+    let has_embed = title.is_some() || description.is_some() || !template.fields.is_empty();
+    if !has_embed {
+        // Nothing configured at all, so dump the payload instead of posting an empty message
+        if content.is_none() {
+            let dump = serde_json::to_string_pretty(payload).unwrap_or_default();
+            let embed =
+                CreateEmbed::new().description(truncate(&format!("```json\n{}\n```", dump), 4096));
+            return (None, Some(embed));
+        }
+        return (content, None);
+    }
+
+    let mut embed = CreateEmbed::new();
+    if let Some(title) = title {
+        embed = embed.title(title);
+    }
+    if let Some(description) = description {
+        embed = embed.description(description);
+    }
+    if let Some(url) = fill(&template.url, 2048) {
+        embed = embed.url(url);
+    }
+    if let Some(color) = template.color.as_deref().and_then(parse_color) {
+        embed = embed.colour(color);
+    }
+    if let Some(footer) = fill(&template.footer, 2048) {
+        embed = embed.footer(CreateEmbedFooter::new(footer));
+    }
+    for field in template.fields.iter().take(25) {
+        let name = truncate(&fill_template(&field.name, payload), 256);
+        let value = truncate(&fill_template(&field.value, payload), 1024);
+        if !name.trim().is_empty() && !value.trim().is_empty() {
+            embed = embed.field(name, value, field.inline);
+        }
+    }
+    (content, Some(embed))
+}
+
+pub fn parse_color(color: &str) -> Option<u32> {
+    let color = color.trim();
+    match color.strip_prefix('#') {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => color.parse().ok(),
+    }
+}
+
+fn truncate(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(limit - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn accepts_valid_signatures() {
+        let body = br#"{"action":"opened"}"#;
+        let signature = sign("hunter2", body);
+        assert!(verify_signature("hunter2", body, &signature));
+        assert!(verify_signature(
+            "hunter2",
+            body,
+            &format!("sha256={}", signature)
+        ));
+        assert!(verify_signature(
+            "hunter2",
+            body,
+            &format!(" sha256={} ", signature)
+        ));
+    }
+
+    #[test]
+    fn rejects_bad_signatures() {
+        let body = br#"{"action":"opened"}"#;
+        let signature = sign("hunter2", body);
+        assert!(!verify_signature("other", body, &signature));
+        assert!(!verify_signature("hunter2", b"{}", &signature));
+        assert!(!verify_signature("hunter2", body, ""));
+        assert!(!verify_signature("hunter2", body, "sha256=not-hex"));
+        assert!(!verify_signature("hunter2", body, &signature[..10]));
+    }
+}